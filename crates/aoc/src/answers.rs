@@ -0,0 +1,111 @@
+use crate::{Answer, Part, PuzzleError};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The accepted answers for one day, stored in the cache as `<part>: <answer>` lines.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Answers {
+    parts: BTreeMap<Part, Answer>,
+}
+
+/// How an answer compares to the recorded one.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: Answer },
+    Unknown,
+}
+
+impl Answers {
+    pub fn get(&self, part: Part) -> Option<&Answer> {
+        self.parts.get(&part)
+    }
+
+    pub fn set(&mut self, part: Part, answer: Answer) {
+        self.parts.insert(part, answer);
+    }
+
+    pub fn check(&self, part: Part, answer: &Answer) -> Verdict {
+        match self.get(part) {
+            None => Verdict::Unknown,
+            Some(expected) if expected == answer => Verdict::Correct,
+            Some(expected) => Verdict::Wrong {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = PuzzleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Answers::default();
+
+        for (n, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (part, answer) = match line.split_once(':') {
+                Some(("1", answer)) => (Part::One, answer),
+                Some(("2", answer)) => (Part::Two, answer),
+                _ => {
+                    return Err(PuzzleError::Input(format!(
+                        "Invalid answer on line {}: '{line}'",
+                        n + 1
+                    )))
+                }
+            };
+
+            answers.set(part, answer.trim().into());
+        }
+
+        Ok(answers)
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (part, answer) in &self.parts {
+            writeln!(f, "{}: {}", part, answer)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Wrong { expected } => write!(f, "wrong, expected {}", expected),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answers() {
+        let answers: Answers = "1: 4665\n2: 1,3,7\n".parse().unwrap();
+        assert_eq!(answers.check(Part::One, &4665.into()), Verdict::Correct);
+        assert_eq!(
+            answers.check(Part::One, &4666.into()),
+            Verdict::Wrong {
+                expected: 4665.into()
+            }
+        );
+        assert_eq!(answers.check(Part::Two, &"1,3,7".into()), Verdict::Correct);
+        assert_eq!(answers.to_string(), "1: 4665\n2: 1,3,7\n");
+
+        let answers: Answers = "2: 12\n".parse().unwrap();
+        assert_eq!(answers.check(Part::One, &12.into()), Verdict::Unknown);
+
+        assert!("3: 12".parse::<Answers>().is_err());
+    }
+}
@@ -0,0 +1,336 @@
+use crate::{InputFetcher, Part, Puzzle, PuzzleResult, Registry, Selection, Skip};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// How many times each puzzle is solved.
+#[derive(Clone, Debug)]
+pub struct BenchOptions {
+    /// Untimed runs before measuring, to warm caches and the allocator.
+    pub warmup: usize,
+    /// Timed runs.
+    pub iterations: usize,
+    /// Leave out parts with these tags.
+    pub skip: Skip,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            iterations: 10,
+            skip: Skip::default(),
+        }
+    }
+}
+
+/// The spread of the samples of one phase.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Stats {
+    #[serde(with = "nanos")]
+    pub min: Duration,
+    #[serde(with = "nanos")]
+    pub median: Duration,
+    #[serde(with = "nanos")]
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// The stats of `samples`, which must not be empty.
+    pub fn of(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let rank = |p: usize| samples[(samples.len() * p).div_ceil(100).max(1) - 1];
+
+        Self {
+            min: samples[0],
+            median: rank(50),
+            p95: rank(95),
+        }
+    }
+}
+
+/// The timings of one puzzle, with the input already read.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Bench {
+    pub year: u16,
+    pub day: u8,
+    pub title: String,
+    pub parse: Stats,
+    pub part1: Option<Stats>,
+    pub part2: Option<Stats>,
+}
+
+impl Bench {
+    /// The stats of each timed phase, in order.
+    pub fn phases(&self) -> impl Iterator<Item = (&'static str, Stats)> {
+        [
+            ("parse", Some(self.parse)),
+            ("part 1", self.part1),
+            ("part 2", self.part2),
+        ]
+        .into_iter()
+        .filter_map(|(phase, stats)| Some((phase, stats?)))
+    }
+}
+
+/// Solves the selected puzzles `warmup + iterations` times each, timing
+/// parsing and the parts separately. Puzzles with every part skipped are
+/// left out.
+pub fn bench(
+    registry: &Registry,
+    inputs: &impl InputFetcher,
+    selection: &Selection,
+    options: &BenchOptions,
+) -> PuzzleResult<Vec<Bench>> {
+    let mut benches = Vec::new();
+    for puzzle in registry.select(selection)? {
+        if let Some(part) = options.skip.select(puzzle, selection.part) {
+            benches.push(bench_puzzle(puzzle, inputs, part, options)?);
+        }
+    }
+    Ok(benches)
+}
+
+fn bench_puzzle(
+    puzzle: &dyn Puzzle,
+    inputs: &impl InputFetcher,
+    part: Option<Part>,
+    options: &BenchOptions,
+) -> PuzzleResult<Bench> {
+    let input = inputs.get_input(puzzle.year(), puzzle.day())?;
+
+    for _ in 0..options.warmup {
+        puzzle.solve(&input, part)?;
+    }
+
+    let mut parse = Vec::new();
+    let mut part1 = Vec::new();
+    let mut part2 = Vec::new();
+    for _ in 0..options.iterations.max(1) {
+        let solved = puzzle.solve(&input, part)?;
+        parse.push(solved.parse_time);
+        for p in solved.parts {
+            match p.part {
+                Part::One => part1.push(p.time),
+                Part::Two => part2.push(p.time),
+            }
+        }
+    }
+
+    let stats = |samples: Vec<Duration>| (!samples.is_empty()).then(|| Stats::of(samples));
+    Ok(Bench {
+        year: puzzle.year().0,
+        day: puzzle.day().0,
+        title: puzzle.title().to_string(),
+        parse: Stats::of(parse),
+        part1: stats(part1),
+        part2: stats(part2),
+    })
+}
+
+/// A phase whose median got slower than in an earlier run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Regression {
+    pub year: u16,
+    pub day: u8,
+    pub phase: &'static str,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl Regression {
+    /// How much slower the phase got, in percent.
+    pub fn percent(&self) -> f64 {
+        (self.after.as_secs_f64() / self.before.as_secs_f64() - 1.0) * 100.0
+    }
+}
+
+/// The phases whose median is more than `threshold` percent slower than in
+/// `baseline`. Phases that only one of the runs timed, e.g. a part left out
+/// with `--part`, are not compared.
+pub fn regressions(baseline: &[Bench], benches: &[Bench], threshold: f64) -> Vec<Regression> {
+    let mut regressions = Vec::new();
+
+    for bench in benches {
+        let Some(before) = baseline
+            .iter()
+            .find(|b| (b.year, b.day) == (bench.year, bench.day))
+        else {
+            continue;
+        };
+
+        for (phase, after) in bench.phases() {
+            let Some((_, before)) = before.phases().find(|&(p, _)| p == phase) else {
+                continue;
+            };
+            let regression = Regression {
+                year: bench.year,
+                day: bench.day,
+                phase,
+                before: before.median,
+                after: after.median,
+            };
+            if !before.median.is_zero() && regression.percent() > threshold {
+                regressions.push(regression);
+            }
+        }
+    }
+
+    regressions
+}
+
+/// Replaces the results in `saved` for the puzzles in `benches`, keeping
+/// the others, in puzzle order.
+pub fn merge(saved: Vec<Bench>, benches: &[Bench]) -> Vec<Bench> {
+    let mut merged: Vec<Bench> = saved
+        .into_iter()
+        .filter(|s| !benches.iter().any(|b| (b.year, b.day) == (s.year, s.day)))
+        .chain(benches.iter().cloned())
+        .collect();
+    merged.sort_by_key(|b| (b.year, b.day));
+    merged
+}
+
+/// The benchmarks as a table, with regressions flagged.
+pub struct Table<'a> {
+    pub benches: &'a [Bench],
+    pub regressions: &'a [Regression],
+}
+
+impl Display for Table<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<4} {:>3}  {:<32} {:<6} {:>10} {:>10} {:>10}",
+            "Year", "Day", "Title", "Phase", "Min", "Median", "P95"
+        )?;
+
+        for bench in self.benches {
+            for (i, (phase, stats)) in bench.phases().enumerate() {
+                let (year, day, title) = if i == 0 {
+                    (bench.year.to_string(), bench.day.to_string(), &*bench.title)
+                } else {
+                    (String::new(), String::new(), "")
+                };
+                write!(
+                    f,
+                    "{year:<4} {day:>3}  {title:<32} {phase:<6} {:>10.1?} {:>10.1?} {:>10.1?}",
+                    stats.min, stats.median, stats.p95
+                )?;
+
+                let regression = self
+                    .regressions
+                    .iter()
+                    .find(|r| (r.year, r.day, r.phase) == (bench.year, bench.day, phase));
+                if let Some(regression) = regression {
+                    write!(
+                        f,
+                        "  {:+.0}% slower than {:.1?}",
+                        regression.percent(),
+                        regression.before
+                    )?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Durations as whole nanoseconds, to keep the results file readable.
+mod nanos {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_nanos() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_nanos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(ms: &[u64]) -> Vec<Duration> {
+        ms.iter().map(|&ms| Duration::from_millis(ms)).collect()
+    }
+
+    fn bench(day: u8, median: u64) -> Bench {
+        let stats = Stats {
+            median: Duration::from_millis(median),
+            ..Stats::default()
+        };
+        Bench {
+            year: 2024,
+            day,
+            title: "Stub".to_string(),
+            parse: stats,
+            part1: Some(stats),
+            part2: None,
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::of(millis(&[5, 1, 4, 2, 3]));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+
+        let stats = Stats::of(millis(&(1..=100).collect::<Vec<_>>()));
+        assert_eq!(stats.median, Duration::from_millis(50));
+        assert_eq!(stats.p95, Duration::from_millis(95));
+    }
+
+    #[test]
+    fn test_regressions() {
+        let baseline = [bench(1, 10), bench(2, 10)];
+        let benches = [bench(1, 11), bench(2, 20), bench(3, 50)];
+
+        let regressions = regressions(&baseline, &benches, 20.0);
+        assert_eq!(regressions.len(), 2);
+        assert_eq!((regressions[0].day, regressions[0].phase), (2, "parse"));
+        assert_eq!(regressions[1].phase, "part 1");
+        assert_eq!(regressions[0].percent().round(), 100.0);
+
+        let merged = merge(baseline.to_vec(), &benches[1..]);
+        assert_eq!(merged, [bench(1, 10), bench(2, 20), bench(3, 50)]);
+    }
+
+    #[test]
+    fn test_regressions_of_other_parts() {
+        let part2_only = |median| Bench {
+            part1: None,
+            part2: bench(1, median).part1,
+            ..bench(1, 10)
+        };
+        let both = |median| Bench {
+            part2: Some(Stats {
+                median: Duration::from_millis(median),
+                ..Stats::default()
+            }),
+            ..bench(1, 10)
+        };
+
+        // Part 1 is not compared to the baseline's part 2
+        assert_eq!(regressions(&[part2_only(1)], &[both(100)], 20.0).len(), 1);
+        assert_eq!(regressions(&[part2_only(1)], &[both(1)], 20.0), []);
+
+        let regressions = regressions(&[both(1)], &[part2_only(100)], 20.0);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].phase, "part 2");
+    }
+
+    #[test]
+    fn test_json() {
+        let json = serde_json::to_string(&bench(1, 2)).unwrap();
+        assert!(json.contains(r#""median":2000000"#), "{json}");
+        assert_eq!(serde_json::from_str::<Bench>(&json).unwrap(), bench(1, 2));
+    }
+}
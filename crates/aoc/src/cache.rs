@@ -1,106 +1,432 @@
-use crate::{PuzzleError, PuzzleResult};
+use crate::calendar::{format_duration, time_to_unlock, wait_for_unlock, Clock, SystemClock};
+use crate::download::{fetch, Retry};
+use crate::page::PuzzlePage;
+use crate::session::{find_session, session_sources, validate_session};
+use crate::{runner, Answer, Answers, Bench, Outcome, Part, PuzzleError, PuzzleResult};
+use std::env;
 use std::fs;
-use std::fs::{create_dir_all, rename, File};
-use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::fs::{create_dir_all, remove_file, rename};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const CACHE_DIR_ENV: &str = "AOC_CACHE_DIR";
 
 #[derive(Debug)]
 pub struct AocCache {
     root: PathBuf,
+    base_url: String,
+    token: Option<String>,
+    session: OnceLock<String>,
+    retry: Retry,
+    clock: Arc<dyn Clock>,
+    wait: bool,
 }
 
 impl Default for AocCache {
+    /// A cache in the root found by [`find_root`], starting from the current directory.
     fn default() -> Self {
-        Self {
-            root: PathBuf::from("cache"),
+        let start = env::current_dir().unwrap_or_default();
+        Self::new(find_root(
+            &start,
+            env::var_os(CACHE_DIR_ENV).map(PathBuf::from),
+            xdg_cache_home(),
+        ))
+    }
+}
+
+/// Where the cache is kept, in order of preference:
+///
+/// 1. `env_dir`, i.e. `$AOC_CACHE_DIR`.
+/// 2. The first `cache` directory in `start` or one of its parents, up to
+///    the workspace root. The workspace's `cache` is used if there is none.
+/// 3. `aoc` in the XDG cache directory, when outside of the workspace.
+pub fn find_root(start: &Path, env_dir: Option<PathBuf>, xdg_dir: Option<PathBuf>) -> PathBuf {
+    if let Some(dir) = env_dir.filter(|dir| !dir.as_os_str().is_empty()) {
+        return dir;
+    }
+
+    for dir in start.ancestors() {
+        let cache = dir.join("cache");
+        if cache.is_dir() || is_workspace_root(dir) {
+            return cache;
         }
     }
+
+    match xdg_dir {
+        Some(dir) => dir.join("aoc"),
+        None => PathBuf::from("cache"),
+    }
+}
+
+pub(crate) fn is_workspace_root(dir: &Path) -> bool {
+    fs::read_to_string(dir.join("Cargo.toml")).is_ok_and(|toml| toml.contains("[workspace]"))
+}
+
+fn xdg_cache_home() -> Option<PathBuf> {
+    env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
 }
 
 impl AocCache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            token: None,
+            session: OnceLock::new(),
+            retry: Retry::default(),
+            clock: Arc::new(SystemClock),
+            wait: false,
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Uses `token` instead of looking for one in the environment and config files.
+    pub fn with_session(mut self, token: &str) -> Self {
+        self.token = Some(token.trim().to_string());
+        self
+    }
+
+    /// Talks to another server than adventofcode.com, e.g. a local one in tests.
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_retry(mut self, retry: Retry) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Sleeps until a puzzle unlocks when asked for it early, instead of failing.
+    pub fn with_wait(mut self, wait: bool) -> Self {
+        self.wait = wait;
+        self
+    }
+
     pub fn get_path(&self, year: u16, day: u8) -> PuzzleResult<PathBuf> {
         let file_path = self.path(year, day);
-        let tmp_file_path = format!("{}.tmp", file_path.display());
 
         // Check if the file already exists, return the stream from the file if it does
         if file_path.is_file() {
             return Ok(file_path);
         }
 
-        // If file doesn't exist, download it to the .tmp file
-        println!("File not found, downloading input.");
+        self.check_unlocked(year, day)?;
 
-        let session = self.get_session();
+        runner::progress("File not found, downloading input.");
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        self.download(&url, &file_path)?;
 
-        if let Some(parent) = PathBuf::from(&tmp_file_path).parent() {
-            create_dir_all(parent).map_err(|e| {
-                PuzzleError::Cache(
-                    format!("Failed to create cache directory {}: {e}", parent.display()),
-                    e.into(),
-                )
-            })?;
+        Ok(file_path)
+    }
+
+    /// The puzzle description rendered as Markdown, also kept next to the input.
+    pub fn description(&self, year: u16, day: u8) -> PuzzleResult<String> {
+        let path = self.day_path(year, day, "md");
+        if path.is_file() {
+            return read_file(&path);
         }
 
-        // Open the .tmp file for writing
-        let mut tmp_file = File::create(&tmp_file_path).map_err(|e| {
-            PuzzleError::Cache(
-                format!("Failed to open file at {}: {}", tmp_file_path, e),
-                e.into(),
-            )
+        let markdown = self.get_page(year, day)?.markdown();
+        write_atomic(&path, markdown.as_bytes())?;
+        Ok(markdown)
+    }
+
+    /// The path to the `n`th sample on the puzzle page, counting from 1.
+    pub fn get_sample_path(&self, year: u16, day: u8, n: usize) -> PuzzleResult<PathBuf> {
+        let path = self.day_path(year, day, &format!("sample{n}.txt"));
+        if path.is_file() {
+            return Ok(path);
+        }
+
+        let samples = self.get_page(year, day)?.samples();
+        let sample = samples.get(n.wrapping_sub(1)).ok_or_else(|| {
+            PuzzleError::Input(format!(
+                "There are {} samples for {year} day {day}, not {n}",
+                samples.len()
+            ))
         })?;
 
-        let url = format!("https://adventofcode.com/{year}/day/{day}/input");
+        write_atomic(&path, sample.as_bytes())?;
+        Ok(path)
+    }
+
+    /// The puzzle page, downloaded if it isn't in the cache. A cached page is
+    /// only downloaded again once the second part has unlocked, see
+    /// [`AocCache::submit_answer`].
+    fn get_page(&self, year: u16, day: u8) -> PuzzleResult<PuzzlePage> {
+        let path = self.day_path(year, day, "html");
+
+        if !path.is_file() {
+            self.check_unlocked(year, day)?;
+            let url = format!("{}/{year}/day/{day}", self.base_url);
+            self.download(&url, &path)?;
+        }
+
+        Ok(PuzzlePage::parse(&read_file(&path)?))
+    }
+
+    /// Drops the cached page and description, which don't have the second
+    /// part yet, so that they are downloaded again when they are needed.
+    /// Samples already extracted from the page are kept.
+    fn forget_page(&self, year: u16, day: u8) -> PuzzleResult<()> {
+        for suffix in ["html", "md"] {
+            let path = self.day_path(year, day, suffix);
+            match remove_file(&path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                    return Err(PuzzleError::Cache(
+                        format!("Can't remove {}: {e}", path.display()),
+                        e.into(),
+                    ))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails, or waits in wait mode, if the puzzle hasn't unlocked yet.
+    fn check_unlocked(&self, year: u16, day: u8) -> PuzzleResult<()> {
+        if self.wait {
+            return wait_for_unlock(self.clock.as_ref(), year, day);
+        }
+
+        match time_to_unlock(self.clock.as_ref(), year, day)? {
+            None => Ok(()),
+            Some(remaining) => Err(PuzzleError::NotUnlocked(format!(
+                "{year} day {day} unlocks in {}, run with --wait to wait for it",
+                format_duration(remaining)
+            ))),
+        }
+    }
+
+    fn download(&self, url: &str, file_path: &Path) -> PuzzleResult<()> {
+        let session = self.session()?;
+        let body = fetch(url, session, &self.retry)?;
+        write_atomic(file_path, &body)
+    }
+
+    /// Submits an answer and records it in the answer store if it was accepted.
+    /// An accepted first part unlocks the second, so the cached puzzle page
+    /// is dropped then.
+    pub fn submit_answer(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &Answer,
+    ) -> PuzzleResult<Outcome> {
+        let session = self.session()?;
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
 
-        // Fetch input via streaming
-        let response = ureq::get(&url)
+        let response = ureq::post(&url)
             .set("Cookie", &format!("session={}", session))
-            .call()
+            .send_form(&[("level", &part.to_string()), ("answer", answer.as_str())])
             .map_err(|e| {
-                PuzzleError::DownloadFailed(format!("Failed to download {url}: {e}"), e.into())
+                PuzzleError::DownloadFailed(format!("Failed to submit to {url}: {e}"), e.into())
             })?;
 
-        // Stream the response into the .tmp file
-        let mut reader = response.into_reader();
-        let mut buffer = [0; 8192]; // 8 KB chunks
+        let body = response.into_string().map_err(|e| {
+            PuzzleError::DownloadFailed(format!("Failed to read reply from {url}: {e}"), e.into())
+        })?;
 
-        while let Ok(bytes_read) = reader.read(&mut buffer) {
-            if bytes_read == 0 {
-                break; // EOF reached
+        let outcome = Outcome::parse(&body);
+        if outcome == Outcome::Right {
+            let mut answers = self.answers(year, day)?;
+            answers.set(part, answer.clone());
+            self.record_answers(year, day, &answers)?;
+            if part == Part::One {
+                self.forget_page(year, day)?;
             }
-            tmp_file.write_all(&buffer[..bytes_read]).map_err(|e| {
-                PuzzleError::Cache(
-                    format!("Can't write to file {}: {e}", tmp_file_path),
-                    e.into(),
-                )
-            })?;
         }
 
-        // Rename the .tmp file to the final file name (this is atomic on most filesystems)
-        rename(&tmp_file_path, &file_path).map_err(|e| {
-            PuzzleError::Cache(
-                format!(
-                    "Can't rename {} to {}: {e}",
-                    tmp_file_path,
-                    file_path.display()
-                ),
-                e.into(),
-            )
+        Ok(outcome)
+    }
+
+    /// The recorded answers for a day, empty if none have been recorded yet.
+    pub fn answers(&self, year: u16, day: u8) -> PuzzleResult<Answers> {
+        let path = self.answers_path(year, day);
+        if !path.is_file() {
+            return Ok(Answers::default());
+        }
+
+        read_file(&path)?.parse()
+    }
+
+    pub fn record_answers(&self, year: u16, day: u8, answers: &Answers) -> PuzzleResult<()> {
+        write_atomic(
+            &self.answers_path(year, day),
+            answers.to_string().as_bytes(),
+        )
+    }
+
+    /// The puzzle title, from the cached puzzle page.
+    pub fn title(&self, year: u16, day: u8) -> PuzzleResult<String> {
+        self.get_page(year, day)?
+            .title()
+            .ok_or_else(|| PuzzleError::Input(format!("No title on the page for {year} day {day}")))
+    }
+
+    /// The results of the last `aoc bench` of each puzzle.
+    pub fn bench_results(&self) -> PuzzleResult<Vec<Bench>> {
+        let path = self.bench_path();
+        if !path.is_file() {
+            return Ok(Vec::new());
+        }
+
+        serde_json::from_str(&read_file(&path)?).map_err(|e| {
+            PuzzleError::Cache(format!("Can't parse {}: {e}", path.display()), e.into())
+        })
+    }
+
+    pub fn record_bench_results(&self, benches: &[Bench]) -> PuzzleResult<()> {
+        let json = serde_json::to_string_pretty(benches).map_err(|e| {
+            PuzzleError::Cache(format!("Can't serialize bench results: {e}"), e.into())
         })?;
+        write_atomic(&self.bench_path(), json.as_bytes())
+    }
 
-        Ok(file_path)
+    /// Where the frames of a part are saved, without an extension, e.g.
+    /// `cache/viz/2024/06.part1`.
+    pub fn viz_path(&self, year: u16, day: u8, part: Part) -> PathBuf {
+        self.root
+            .join("viz")
+            .join(format!("{}/{:02}.part{}", year, day, part))
+    }
+
+    fn bench_path(&self) -> PathBuf {
+        self.root.join("bench.json")
     }
 
     fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.day_path(year, day, "txt")
+    }
+
+    fn answers_path(&self, year: u16, day: u8) -> PathBuf {
+        self.day_path(year, day, "answers.txt")
+    }
+
+    /// A file next to the input, e.g. `cache/aoc/2024/06.<suffix>`.
+    fn day_path(&self, year: u16, day: u8, suffix: &str) -> PathBuf {
         self.root
             .join("aoc")
-            .join(format!("{}/{:02}.txt", year, day))
+            .join(format!("{}/{:02}.{}", year, day, suffix))
     }
 
-    fn get_session(&self) -> String {
-        let path = self.root.join("session.txt");
-        fs::read_to_string(path)
-            .expect("Session file not found")
-            .trim()
-            .to_string()
+    /// The session token, validated with the server the first time it is needed.
+    fn session(&self) -> PuzzleResult<&str> {
+        if let Some(session) = self.session.get() {
+            return Ok(session);
+        }
+
+        let session = match &self.token {
+            Some(token) => token.clone(),
+            None => find_session(&session_sources(&self.root))?,
+        };
+        validate_session(&self.base_url, &session)?;
+        Ok(self.session.get_or_init(|| session))
+    }
+}
+
+/// Writes to a `.tmp` file that is renamed into place, so that `path` is
+/// either complete or missing. The `.tmp` file is removed on failure.
+///
+/// Every file in the cache is written this way, since inputs and samples
+/// are memory-mapped by [`Input::bytes`](crate::Input::bytes) and must not
+/// change under the mapping.
+fn write_atomic(path: &Path, contents: &[u8]) -> PuzzleResult<()> {
+    let tmp_path = PathBuf::from(format!("{}.tmp", path.display()));
+
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent).map_err(|e| {
+            PuzzleError::Cache(
+                format!("Failed to create cache directory {}: {e}", parent.display()),
+                e.into(),
+            )
+        })?;
+    }
+
+    let result = fs::write(&tmp_path, contents)
+        .map_err(|e| {
+            PuzzleError::Cache(
+                format!("Can't write to file {}: {e}", tmp_path.display()),
+                e.into(),
+            )
+        })
+        .and_then(|_| {
+            // Atomic on most filesystems
+            rename(&tmp_path, path).map_err(|e| {
+                PuzzleError::Cache(
+                    format!(
+                        "Can't rename {} to {}: {e}",
+                        tmp_path.display(),
+                        path.display()
+                    ),
+                    e.into(),
+                )
+            })
+        });
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+
+    result
+}
+
+fn read_file(path: &Path) -> PuzzleResult<String> {
+    fs::read_to_string(path)
+        .map_err(|e| PuzzleError::Cache(format!("Can't read {}: {e}", path.display()), e.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = dir.path().join("workspace");
+        let crate_dir = workspace.join("crates").join("aoc24");
+        fs::create_dir_all(&crate_dir).unwrap();
+        fs::write(workspace.join("Cargo.toml"), "[workspace]\n").unwrap();
+        fs::write(crate_dir.join("Cargo.toml"), "[package]\n").unwrap();
+
+        let xdg = Some(dir.path().join("xdg"));
+        assert_eq!(
+            find_root(&crate_dir, None, xdg.clone()),
+            workspace.join("cache")
+        );
+
+        fs::create_dir_all(workspace.join("crates").join("cache")).unwrap();
+        assert_eq!(
+            find_root(&crate_dir, None, xdg.clone()),
+            workspace.join("crates").join("cache")
+        );
+
+        assert_eq!(
+            find_root(&crate_dir, Some("/tmp/aoc".into()), xdg.clone()),
+            PathBuf::from("/tmp/aoc")
+        );
+
+        let outside = dir.path().join("elsewhere");
+        fs::create_dir_all(&outside).unwrap();
+        assert_eq!(
+            find_root(&outside, None, xdg),
+            dir.path().join("xdg").join("aoc")
+        );
     }
 }
@@ -0,0 +1,119 @@
+use crate::{runner, PuzzleError, PuzzleResult};
+use std::fmt::Debug;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const FIRST_YEAR: u16 = 2015;
+pub const LAST_DAY: u8 = 25;
+
+/// Puzzles unlock at midnight in UTC-5.
+const UNLOCK_HOUR_UTC: u64 = 5;
+
+/// The time source for waiting on puzzles to unlock, replaceable in tests.
+pub trait Clock: Debug + Send + Sync {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+#[derive(Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// The instant a puzzle unlocks, midnight UTC-5 on December `day`.
+pub fn unlock_time(year: u16, day: u8) -> PuzzleResult<SystemTime> {
+    if year < FIRST_YEAR || !(1..=LAST_DAY).contains(&day) {
+        return Err(PuzzleError::Input(format!(
+            "There is no puzzle for {year} day {day}"
+        )));
+    }
+
+    let days = days_from_civil(year as i64, 12, day as i64) as u64;
+    Ok(UNIX_EPOCH + Duration::from_secs(days * 24 * 3600 + UNLOCK_HOUR_UTC * 3600))
+}
+
+/// How long until the puzzle unlocks, or `None` if it already has.
+pub fn time_to_unlock(clock: &dyn Clock, year: u16, day: u8) -> PuzzleResult<Option<Duration>> {
+    let unlock = unlock_time(year, day)?;
+    Ok(unlock
+        .duration_since(clock.now())
+        .ok()
+        .filter(|d| !d.is_zero()))
+}
+
+/// Blocks until the puzzle has unlocked.
+pub fn wait_for_unlock(clock: &dyn Clock, year: u16, day: u8) -> PuzzleResult<()> {
+    while let Some(remaining) = time_to_unlock(clock, year, day)? {
+        runner::progress(format!(
+            "{year} day {day} unlocks in {}, waiting",
+            format_duration(remaining)
+        ));
+        clock.sleep(remaining);
+    }
+
+    Ok(())
+}
+
+/// A duration in days, hours, minutes and seconds, e.g. `2d 3h 0m 12s`.
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (days, hours, minutes, seconds) =
+        (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{seconds}s"),
+        (0, 0, _) => format!("{minutes}m {seconds}s"),
+        (0, _, _) => format!("{hours}h {minutes}m {seconds}s"),
+        _ => format!("{days}d {hours}h {minutes}m {seconds}s"),
+    }
+}
+
+/// Days since 1970-01-01 for a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(time: SystemTime) -> u64 {
+        time.duration_since(UNIX_EPOCH).unwrap().as_secs()
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        // 2024-12-01T05:00:00Z
+        assert_eq!(secs(unlock_time(2024, 1).unwrap()), 1733029200);
+        // 2015-12-25T05:00:00Z
+        assert_eq!(secs(unlock_time(2015, 25).unwrap()), 1451019600);
+        assert!(unlock_time(2024, 26).is_err());
+        assert!(unlock_time(2014, 1).is_err());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(12)), "12s");
+        assert_eq!(
+            format_duration(Duration::from_secs(3 * 3600 + 5)),
+            "3h 0m 5s"
+        );
+        assert_eq!(
+            format_duration(Duration::from_secs(86400 + 61)),
+            "1d 0h 1m 1s"
+        );
+    }
+}
@@ -0,0 +1,494 @@
+use crate::calendar::{FIRST_YEAR, LAST_DAY};
+use crate::viz;
+use crate::DEFAULT_BASE_URL;
+use crate::{AocCache, BenchOptions, Part, PuzzleError, PuzzleResult, RunOptions, Skip, Tag};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug, Parser)]
+#[command(about = "Advent of Code solutions")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// The Advent of Code server to download from and submit to
+    #[arg(long, global = true, default_value = DEFAULT_BASE_URL)]
+    pub base_url: String,
+
+    /// The cache directory, instead of $AOC_CACHE_DIR or the one found from the current directory
+    #[arg(long, global = true)]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Wait for puzzles that haven't unlocked yet instead of failing
+    #[arg(long, global = true)]
+    pub wait: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the selected puzzles
+    Run(RunArgs),
+    /// Submit the answer to one part, solving it first if no answer is given
+    Submit(SubmitArgs),
+    /// Time the selected puzzles and compare with the last benchmark
+    Bench(BenchArgs),
+    /// Start a new day from the template, with its title, sample and input
+    New(NewArgs),
+    /// Step through the simulation of a day, forward and back
+    Debug(DebugArgs),
+}
+
+#[derive(Debug, Default, Args)]
+pub struct RunArgs {
+    /// Years and days to run, e.g. `2024 6`, `2024 1-5,7`, `2015 latest` or `all`
+    pub selection: Vec<String>,
+
+    /// Only run one of the parts
+    #[arg(long, short)]
+    pub part: Option<Part>,
+
+    /// Save answers for parts that have no recorded answer yet
+    #[arg(long)]
+    pub record: bool,
+
+    /// Give up on a puzzle after this many seconds and go on with the next
+    #[arg(long)]
+    pub timeout: Option<f64>,
+
+    /// How many puzzles to solve at the same time
+    #[arg(long, short, default_value_t = 1)]
+    pub jobs: usize,
+
+    #[command(flatten)]
+    pub tags: TagArgs,
+
+    /// Record the frames the solutions draw and play them, or save them in the cache
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "terminal"
+    )]
+    pub viz: Option<viz::Format>,
+}
+
+/// Which tagged parts to run. Broken and experimental ones are skipped unless included.
+#[derive(Debug, Default, Args)]
+pub struct TagArgs {
+    /// Skip parts with these tags
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "TAG")]
+    pub skip: Vec<Tag>,
+
+    /// Run parts with these tags that are skipped by default
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "TAG")]
+    pub include: Vec<Tag>,
+}
+
+/// An empty command line means `run` with no arguments.
+impl Default for Command {
+    fn default() -> Self {
+        Command::Run(RunArgs::default())
+    }
+}
+
+#[derive(Debug, Default, Args)]
+pub struct BenchArgs {
+    /// Years and days to benchmark, as for `run`
+    pub selection: Vec<String>,
+
+    /// Only benchmark one of the parts
+    #[arg(long, short)]
+    pub part: Option<Part>,
+
+    /// Untimed runs before measuring
+    #[arg(long, default_value_t = 3)]
+    pub warmup: usize,
+
+    /// Timed runs
+    #[arg(long, short = 'n', default_value_t = 10)]
+    pub iterations: usize,
+
+    /// Print the results as a table or as JSON
+    #[arg(long, value_enum, default_value_t = BenchFormat::Table)]
+    pub format: BenchFormat,
+
+    /// Flag phases whose median is this many percent slower than last time
+    #[arg(long, default_value_t = 10.0)]
+    pub threshold: f64,
+
+    /// Don't save the results for the next comparison
+    #[arg(long)]
+    pub no_save: bool,
+
+    #[command(flatten)]
+    pub tags: TagArgs,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum BenchFormat {
+    #[default]
+    Table,
+    Json,
+}
+
+#[derive(Debug, Args)]
+pub struct NewArgs {
+    pub year: u16,
+    pub day: u8,
+}
+
+#[derive(Debug, Args)]
+pub struct DebugArgs {
+    pub year: u16,
+    pub day: u8,
+
+    /// The part to step through
+    #[arg(long, short, default_value = "1")]
+    pub part: Part,
+
+    /// Use this sample from the puzzle page instead of the input
+    #[arg(long)]
+    pub sample: Option<usize>,
+}
+
+#[derive(Debug, Args)]
+pub struct SubmitArgs {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub answer: Option<String>,
+}
+
+impl Cli {
+    pub fn cache(&self) -> AocCache {
+        let cache = match &self.cache_dir {
+            Some(dir) => AocCache::new(dir),
+            None => AocCache::default(),
+        };
+        cache.with_base_url(&self.base_url).with_wait(self.wait)
+    }
+}
+
+impl RunArgs {
+    pub fn selection(&self) -> PuzzleResult<Selection> {
+        let mut selection = Selection::parse(&self.selection)?;
+        selection.part = self.part;
+        Ok(selection)
+    }
+
+    pub fn options(&self) -> RunOptions {
+        RunOptions {
+            record: self.record,
+            timeout: self.timeout.map(Duration::from_secs_f64),
+            jobs: self.jobs,
+            skip: self.tags.skip(),
+            viz: self.viz,
+        }
+    }
+}
+
+impl BenchArgs {
+    pub fn selection(&self) -> PuzzleResult<Selection> {
+        let mut selection = Selection::parse(&self.selection)?;
+        selection.part = self.part;
+        Ok(selection)
+    }
+
+    pub fn options(&self) -> BenchOptions {
+        BenchOptions {
+            warmup: self.warmup,
+            iterations: self.iterations,
+            skip: self.tags.skip(),
+        }
+    }
+}
+
+impl TagArgs {
+    pub fn skip(&self) -> Skip {
+        Skip::new(&self.skip, &self.include)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DaySelection {
+    All,
+    Latest,
+    Days(Vec<u8>),
+}
+
+impl DaySelection {
+    /// Resolves the selection against the days that have solutions.
+    pub fn resolve(&self, available: &[u8]) -> Vec<u8> {
+        match self {
+            DaySelection::All => available.to_vec(),
+            DaySelection::Latest => available.iter().max().copied().into_iter().collect(),
+            DaySelection::Days(days) => days
+                .iter()
+                .copied()
+                .filter(|day| available.contains(day))
+                .collect(),
+        }
+    }
+
+    fn merge(self, other: DaySelection) -> DaySelection {
+        match (self, other) {
+            (DaySelection::Days(mut a), DaySelection::Days(b)) => {
+                a.extend(b);
+                a.sort();
+                a.dedup();
+                DaySelection::Days(a)
+            }
+            (_, other) => other,
+        }
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = PuzzleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => return Ok(DaySelection::All),
+            "latest" => return Ok(DaySelection::Latest),
+            _ => {}
+        }
+
+        let parse_day = |s: &str| match s.trim().parse::<u8>() {
+            Ok(day) if (1..=LAST_DAY).contains(&day) => Ok(day),
+            _ => Err(PuzzleError::Input(format!(
+                "Invalid day '{s}', expected 1-{LAST_DAY}"
+            ))),
+        };
+
+        let mut days = Vec::new();
+        for item in s.split(',') {
+            match item.split_once('-') {
+                Some((first, last)) => days.extend(parse_day(first)?..=parse_day(last)?),
+                None => days.push(parse_day(item)?),
+            }
+        }
+
+        days.sort();
+        days.dedup();
+        Ok(DaySelection::Days(days))
+    }
+}
+
+/// A year, or every year when `None`, and the days to run for it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Target {
+    pub year: Option<u16>,
+    pub days: DaySelection,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Selection {
+    pub targets: Vec<Target>,
+    pub part: Option<Part>,
+}
+
+impl Default for Selection {
+    fn default() -> Self {
+        Self {
+            targets: vec![Target {
+                year: None,
+                days: DaySelection::All,
+            }],
+            part: None,
+        }
+    }
+}
+
+impl Selection {
+    /// Parses the positional `run` arguments.
+    ///
+    /// A year starts a new target and the day specifications following it
+    /// narrow it down. Days given before any year apply to every year.
+    pub fn parse<T: AsRef<str>>(args: &[T]) -> PuzzleResult<Self> {
+        let mut targets: Vec<Target> = Vec::new();
+        let mut explicit_days = false;
+
+        for arg in args {
+            let arg = arg.as_ref();
+            match arg.parse::<u16>() {
+                Ok(year) if year >= FIRST_YEAR => {
+                    targets.push(Target {
+                        year: Some(year),
+                        days: DaySelection::All,
+                    });
+                    explicit_days = false;
+                }
+                _ if arg == "all" && targets.is_empty() => targets.push(Target {
+                    year: None,
+                    days: DaySelection::All,
+                }),
+                _ => {
+                    let days = arg.parse::<DaySelection>()?;
+                    match targets.last_mut() {
+                        Some(target) if explicit_days => {
+                            target.days = target.days.clone().merge(days);
+                        }
+                        Some(target) => target.days = days,
+                        None => targets.push(Target { year: None, days }),
+                    }
+                    explicit_days = true;
+                }
+            }
+        }
+
+        if targets.is_empty() {
+            return Ok(Self::default());
+        }
+
+        Ok(Self {
+            targets,
+            part: None,
+        })
+    }
+
+    /// The selected days for `year`, in order, out of the `available` ones.
+    pub fn days(&self, year: u16, available: &[u8]) -> Vec<u8> {
+        let mut days: Vec<u8> = self
+            .targets
+            .iter()
+            .filter(|target| target.year.is_none_or(|y| y == year))
+            .flat_map(|target| target.days.resolve(available))
+            .collect();
+
+        days.sort();
+        days.dedup();
+        days
+    }
+
+    /// The years named explicitly on the command line.
+    pub fn years(&self) -> impl Iterator<Item = u16> + '_ {
+        self.targets.iter().filter_map(|target| target.year)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_args(cli: Cli) -> RunArgs {
+        match cli.command.unwrap_or_default() {
+            Command::Run(args) => args,
+            command => panic!("Unexpected command {command:?}"),
+        }
+    }
+
+    fn days(s: &str) -> DaySelection {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_day_selection() {
+        assert_eq!(days("6"), DaySelection::Days(vec![6]));
+        assert_eq!(days("1-3,7"), DaySelection::Days(vec![1, 2, 3, 7]));
+        assert_eq!(days("3,1,2-3"), DaySelection::Days(vec![1, 2, 3]));
+        assert_eq!(days("latest"), DaySelection::Latest);
+        assert!("26".parse::<DaySelection>().is_err());
+        assert!("0".parse::<DaySelection>().is_err());
+        assert!("x".parse::<DaySelection>().is_err());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let available = [1, 2, 3, 5];
+        assert_eq!(days("all").resolve(&available), vec![1, 2, 3, 5]);
+        assert_eq!(days("latest").resolve(&available), vec![5]);
+        assert_eq!(days("3-4").resolve(&available), vec![3]);
+        assert_eq!(DaySelection::Latest.resolve(&[]), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_selection() {
+        let available = [1, 2, 3, 4, 5, 6];
+
+        let selection = Selection::parse::<&str>(&[]).unwrap();
+        assert_eq!(selection.days(2024, &available), available);
+
+        let selection = Selection::parse(&["2024", "6"]).unwrap();
+        assert_eq!(selection.days(2024, &available), vec![6]);
+        assert_eq!(selection.days(2015, &available), Vec::<u8>::new());
+
+        let selection = Selection::parse(&["2024", "1-2", "5", "2015", "latest"]).unwrap();
+        assert_eq!(selection.days(2024, &available), vec![1, 2, 5]);
+        assert_eq!(selection.days(2015, &available), vec![6]);
+        assert_eq!(selection.years().collect::<Vec<_>>(), vec![2024, 2015]);
+
+        let selection = Selection::parse(&["3"]).unwrap();
+        assert_eq!(selection.days(2015, &available), vec![3]);
+        assert_eq!(selection.days(2024, &available), vec![3]);
+    }
+
+    #[test]
+    fn test_cli() {
+        let cli = Cli::try_parse_from(["aoc", "run", "2024", "6", "--part", "2"]).unwrap();
+        let selection = run_args(cli).selection().unwrap();
+        assert_eq!(selection.part, Some(Part::Two));
+        assert_eq!(selection.days(2024, &[5, 6]), vec![6]);
+
+        let cli = Cli::try_parse_from(["aoc", "run", "--record"]).unwrap();
+        assert!(run_args(cli).options().record);
+
+        let cli = Cli::try_parse_from(["aoc", "run", "--timeout", "2.5"]).unwrap();
+        let timeout = run_args(cli).options().timeout;
+        assert_eq!(timeout, Some(Duration::from_millis(2500)));
+
+        let cli = Cli::try_parse_from(["aoc", "run", "-j", "4"]).unwrap();
+        assert_eq!(run_args(cli).options().jobs, 4);
+
+        let cli = Cli::try_parse_from(["aoc", "run", "--skip", "slow", "--include", "broken"]);
+        let skip = run_args(cli.unwrap()).options().skip;
+        assert_eq!(skip, Skip::new(&[Tag::Slow], &[Tag::Broken]));
+        assert!(Cli::try_parse_from(["aoc", "run", "--skip", "fast"]).is_err());
+
+        let args = run_args(Cli::try_parse_from(["aoc", "run", "--viz", "2024", "6"]).unwrap());
+        assert_eq!(args.options().viz, Some(viz::Format::Terminal));
+        assert_eq!(args.selection.len(), 2);
+        let cli = Cli::try_parse_from(["aoc", "run", "--viz=gif"]).unwrap();
+        assert_eq!(run_args(cli).options().viz, Some(viz::Format::Gif));
+
+        let args = run_args(Cli::try_parse_from(["aoc"]).unwrap());
+        assert_eq!(args.selection().unwrap(), Selection::default());
+        assert_eq!(args.options().skip, Skip::default());
+
+        assert!(Cli::try_parse_from(["aoc", "run", "--part", "3"]).is_err());
+
+        let cli = Cli::try_parse_from(["aoc", "bench", "2024", "-n", "5", "--format", "json"]);
+        match cli.unwrap().command {
+            Some(Command::Bench(args)) => {
+                assert_eq!(args.options().iterations, 5);
+                assert_eq!(args.options().warmup, 3);
+                assert_eq!(args.format, BenchFormat::Json);
+                assert_eq!(args.selection().unwrap().days(2024, &[1, 2]), vec![1, 2]);
+            }
+            command => panic!("Unexpected command {command:?}"),
+        }
+
+        let cli = Cli::try_parse_from(["aoc", "submit", "2024", "6", "2", "--base-url", "x"]);
+        let cli = cli.unwrap();
+        assert_eq!(cli.base_url, "x");
+        match cli.command {
+            Some(Command::Submit(args)) => {
+                assert_eq!((args.year, args.day, args.part), (2024, 6, Part::Two));
+                assert_eq!(args.answer, None);
+            }
+            command => panic!("Unexpected command {command:?}"),
+        }
+
+        let cli = Cli::try_parse_from(["aoc", "debug", "2024", "15", "-p", "2", "--sample", "1"]);
+        match cli.unwrap().command {
+            Some(Command::Debug(args)) => {
+                assert_eq!((args.year, args.day, args.part), (2024, 15, Part::Two));
+                assert_eq!(args.sample, Some(1));
+            }
+            command => panic!("Unexpected command {command:?}"),
+        }
+    }
+}
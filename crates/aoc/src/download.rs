@@ -0,0 +1,134 @@
+use crate::{runner, PuzzleError, PuzzleResult};
+use std::io::Read;
+use std::thread;
+use std::time::Duration;
+
+const NOT_UNLOCKED: &str = "Please don't repeatedly request this endpoint before it unlocks";
+const LOG_IN: &str = "Please log in to get your puzzle input";
+
+/// How often, and how patiently, transient download failures are retried.
+#[derive(Clone, Debug)]
+pub struct Retry {
+    pub retries: u32,
+    /// The delay before the first retry, doubled for every retry after it.
+    pub delay: Duration,
+}
+
+impl Default for Retry {
+    fn default() -> Self {
+        Self {
+            retries: 3,
+            delay: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Why a request failed.
+#[derive(Debug)]
+enum Failure {
+    NotUnlocked,
+    BadSession,
+    /// Server errors and dropped connections, which may go away if retried.
+    Transient(String, Box<dyn std::error::Error + Send + Sync>),
+    Permanent(String, Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl Failure {
+    fn into_error(self, url: &str) -> PuzzleError {
+        match self {
+            Failure::NotUnlocked => PuzzleError::NotUnlocked(format!("{url} is not unlocked yet")),
+            Failure::BadSession => PuzzleError::InvalidSession(format!(
+                "{url} asks to log in, the session token is likely expired"
+            )),
+            Failure::Transient(msg, error) | Failure::Permanent(msg, error) => {
+                PuzzleError::DownloadFailed(format!("Failed to download {url}: {msg}"), error)
+            }
+        }
+    }
+}
+
+/// Downloads `url` into memory, retrying transient failures with backoff.
+///
+/// Error pages are never returned as content, not even when they come with a
+/// 200 status.
+pub fn fetch(url: &str, session: &str, retry: &Retry) -> PuzzleResult<Vec<u8>> {
+    let mut attempt = 0;
+    loop {
+        match fetch_once(url, session) {
+            Ok(body) => return Ok(body),
+            Err(Failure::Transient(msg, _)) if attempt < retry.retries => {
+                let delay = retry.delay * 2u32.pow(attempt);
+                runner::progress(format!(
+                    "Failed to download {url}: {msg}, retrying in {delay:.1?}"
+                ));
+                thread::sleep(delay);
+                attempt += 1;
+            }
+            Err(failure) => return Err(failure.into_error(url)),
+        }
+    }
+}
+
+fn fetch_once(url: &str, session: &str) -> Result<Vec<u8>, Failure> {
+    let response = ureq::get(url)
+        .set("Cookie", &format!("session={}", session))
+        .call();
+
+    let (status, response) = match response {
+        Ok(response) => (response.status(), response),
+        Err(ureq::Error::Status(status, response)) => (status, response),
+        Err(ureq::Error::Transport(transport)) => {
+            return Err(Failure::Transient(transport.to_string(), transport.into()))
+        }
+    };
+
+    let mut body = Vec::new();
+    response
+        .into_reader()
+        .read_to_end(&mut body)
+        .map_err(|e| Failure::Transient(format!("Failed to read the response: {e}"), e.into()))?;
+
+    classify(status, &body).map(|_| body)
+}
+
+fn classify(status: u16, body: &[u8]) -> Result<(), Failure> {
+    let text = String::from_utf8_lossy(body);
+    if text.contains(NOT_UNLOCKED) {
+        return Err(Failure::NotUnlocked);
+    }
+    if text.contains(LOG_IN) {
+        return Err(Failure::BadSession);
+    }
+
+    let msg = format!("Server replied {status}");
+    match status {
+        200..=299 => Ok(()),
+        400 | 401 | 403 => Err(Failure::BadSession),
+        429 | 500..=599 => Err(Failure::Transient(msg.clone(), msg.into())),
+        _ => Err(Failure::Permanent(msg.clone(), msg.into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert!(classify(200, b"1 2 3\n").is_ok());
+        assert!(matches!(
+            classify(404, b"Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available."),
+            Err(Failure::NotUnlocked)
+        ));
+        assert!(matches!(
+            classify(
+                200,
+                b"Puzzle inputs differ by user.  Please log in to get your puzzle input."
+            ),
+            Err(Failure::BadSession)
+        ));
+        assert!(matches!(classify(400, b""), Err(Failure::BadSession)));
+        assert!(matches!(classify(502, b""), Err(Failure::Transient(..))));
+        assert!(matches!(classify(404, b""), Err(Failure::Permanent(..))));
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{Day, Year};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::{fmt, io};
+
+pub type PuzzleResult<T> = Result<T, PuzzleError>;
+
+#[derive(Debug)]
+pub enum PuzzleError {
+    IO {
+        msg: String,
+        error: io::Error,
+    },
+    Input(String),
+    /// Input that doesn't have the expected shape, at a 1-based line and
+    /// column. The puzzle is filled in by the runner when it is known.
+    Parse {
+        year: Option<Year>,
+        day: Option<Day>,
+        line: usize,
+        col: usize,
+        snippet: String,
+        expected: String,
+    },
+    Verification(String),
+    Solution(String),
+    DownloadFailed(String, Box<dyn Error + Send + Sync>),
+    Cache(String, Box<dyn Error + Send + Sync>),
+    NotUnlocked(String),
+    MissingSession {
+        searched: Vec<String>,
+    },
+    InvalidSession(String),
+}
+
+impl PuzzleError {
+    /// Attributes a parse error to a puzzle.
+    pub fn in_puzzle(self, year: Year, day: Day) -> Self {
+        match self {
+            PuzzleError::Parse {
+                line,
+                col,
+                snippet,
+                expected,
+                ..
+            } => PuzzleError::Parse {
+                year: Some(year),
+                day: Some(day),
+                line,
+                col,
+                snippet,
+                expected,
+            },
+            error => error,
+        }
+    }
+
+    /// Moves a parse error in a single line to line `number` of the input.
+    pub fn on_line(self, number: usize) -> Self {
+        match self {
+            PuzzleError::Parse {
+                year,
+                day,
+                line,
+                col,
+                snippet,
+                expected,
+            } => PuzzleError::Parse {
+                year,
+                day,
+                line: line + number - 1,
+                col,
+                snippet,
+                expected,
+            },
+            error => error,
+        }
+    }
+
+    /// The error with the offending input and a caret under the column of
+    /// a parse error, for showing to a person.
+    pub fn pretty(&self) -> String {
+        match self {
+            PuzzleError::Parse {
+                line, col, snippet, ..
+            } => {
+                let gutter = line.to_string();
+                let indent: String = snippet
+                    .chars()
+                    .take(col - 1)
+                    .map(|c| if c == '\t' { c } else { ' ' })
+                    .collect();
+                format!(
+                    "{self}\n{:gutter_width$} |\n{gutter} | {snippet}\n{:gutter_width$} | {indent}^",
+                    "",
+                    "",
+                    gutter_width = gutter.len()
+                )
+            }
+            error => error.to_string(),
+        }
+    }
+}
+
+impl Error for PuzzleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PuzzleError::IO { error, .. } => Some(error),
+            PuzzleError::DownloadFailed(_, error) | PuzzleError::Cache(_, error) => {
+                Some(error.as_ref())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Display for PuzzleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PuzzleError::IO { msg, .. }
+            | PuzzleError::Input(msg)
+            | PuzzleError::Verification(msg)
+            | PuzzleError::Solution(msg)
+            | PuzzleError::DownloadFailed(msg, _)
+            | PuzzleError::Cache(msg, _)
+            | PuzzleError::NotUnlocked(msg)
+            | PuzzleError::InvalidSession(msg) => write!(f, "{msg}"),
+            PuzzleError::Parse {
+                year,
+                day,
+                line,
+                col,
+                expected,
+                ..
+            } => {
+                if let (Some(year), Some(day)) = (year, day) {
+                    write!(f, "{year} day {day}: ")?;
+                }
+                write!(f, "Line {line}, column {col}: expected {expected}")
+            }
+            PuzzleError::MissingSession { searched } => write!(
+                f,
+                "No session cookie found, looked in {}",
+                searched.join(", ")
+            ),
+        }
+    }
+}
+
+impl From<io::Error> for PuzzleError {
+    fn from(error: io::Error) -> Self {
+        PuzzleError::IO {
+            msg: "IO error occurred".to_string(),
+            error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source() {
+        let error = PuzzleError::from(io::Error::other("disk on fire"));
+        assert_eq!(error.source().unwrap().to_string(), "disk on fire");
+        assert!(PuzzleError::Input("bad".into()).source().is_none());
+    }
+
+    #[test]
+    fn test_pretty() {
+        let error = PuzzleError::Parse {
+            year: None,
+            day: None,
+            line: 1,
+            col: 4,
+            snippet: "p=0,x v=3,-3".into(),
+            expected: "an integer".into(),
+        }
+        .on_line(12)
+        .in_puzzle(Year(2024), Day(14));
+
+        assert_eq!(
+            error.pretty(),
+            "2024 day 14: Line 12, column 4: expected an integer\n   |\n12 | p=0,x v=3,-3\n   |    ^"
+        );
+    }
+}
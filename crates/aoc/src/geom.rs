@@ -0,0 +1,327 @@
+use crate::grid::Pos;
+use crate::{PuzzleError, PuzzleResult};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point on a plane with `y` growing downwards, like rows in a grid.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point<T = i64> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// The distance walking along the axes.
+    pub fn manhattan(self, other: Self) -> T {
+        let dx = self.x.max(other.x) - self.x.min(other.x);
+        let dy = self.y.max(other.y) - self.y.min(other.y);
+        dx + dy
+    }
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    /// The point one step away in `direction`.
+    pub fn step(self, direction: impl Into<Direction8>) -> Self {
+        self + direction.into().offset()
+    }
+
+    /// The four orthogonal neighbours, clockwise from north.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// All eight neighbours, clockwise from north.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Direction8::ALL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// The grid position of the point, if it isn't left of or above the grid.
+    pub fn pos(self) -> Option<Pos> {
+        Some((self.y.try_into().ok()?, self.x.try_into().ok()?))
+    }
+}
+
+impl From<Pos> for Point {
+    fn from((row, col): Pos) -> Self {
+        Point::new(col as i64, row as i64)
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Self;
+
+    fn add(self, direction: Direction) -> Self {
+        self.step(direction)
+    }
+}
+
+impl AddAssign<Direction> for Point {
+    fn add_assign(&mut self, direction: Direction) {
+        *self = self.step(direction);
+    }
+}
+
+/// One of the four orthogonal directions, in clockwise order.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// The position in [`Direction::ALL`], for indexing per-direction arrays.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self.index() + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self.index() + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self.index() + 2) % 4]
+    }
+
+    pub fn offset(self) -> Point {
+        Direction8::from(self).offset()
+    }
+
+    /// A single bit, so that sets of directions fit in a `u8`.
+    pub fn mask(self) -> u8 {
+        1 << self.index()
+    }
+
+    /// The direction for a mask with exactly one bit set.
+    pub fn from_mask(mask: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.mask() == mask)
+    }
+
+    /// The direction drawn as one of `^>v<`.
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = PuzzleError;
+
+    /// Parses arrows (`^>v<`), compass points (`NESW`) and `UDLR`.
+    fn try_from(c: char) -> PuzzleResult<Self> {
+        match c {
+            '^' | 'N' | 'U' => Ok(Direction::North),
+            '>' | 'E' | 'R' => Ok(Direction::East),
+            'v' | 'S' | 'D' => Ok(Direction::South),
+            '<' | 'W' | 'L' => Ok(Direction::West),
+            _ => Err(PuzzleError::Input(format!("Not a direction: '{c}'"))),
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+/// One of the eight directions including diagonals, in clockwise order.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    /// The position in [`Direction8::ALL`], for indexing per-direction arrays.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The next direction clockwise, 45 degrees to the right.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self.index() + 1) % 8]
+    }
+
+    /// The next direction counterclockwise, 45 degrees to the left.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self.index() + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self.index() + 4) % 8]
+    }
+
+    pub fn offset(self) -> Point {
+        match self {
+            Direction8::North => Point::new(0, -1),
+            Direction8::NorthEast => Point::new(1, -1),
+            Direction8::East => Point::new(1, 0),
+            Direction8::SouthEast => Point::new(1, 1),
+            Direction8::South => Point::new(0, 1),
+            Direction8::SouthWest => Point::new(-1, 1),
+            Direction8::West => Point::new(-1, 0),
+            Direction8::NorthWest => Point::new(-1, -1),
+        }
+    }
+
+    /// A single bit, so that sets of directions fit in a `u8`.
+    pub fn mask(self) -> u8 {
+        1 << self.index()
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        Direction8::ALL[direction.index() * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_direction() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::East.reverse(), Direction::West);
+        assert_eq!(
+            Direction::from_mask(Direction::South.mask()),
+            Some(Direction::South)
+        );
+        assert_eq!(Direction::from_mask(0b11), None);
+        assert_eq!(
+            "^>v<NESWUDLR"
+                .chars()
+                .map(|c| Direction::try_from(c).unwrap().arrow())
+                .collect::<String>(),
+            "^>v<^>v<^v<>"
+        );
+        assert!(Direction::try_from('x').is_err());
+    }
+
+    #[test]
+    fn test_direction8() {
+        assert_eq!(Direction8::from(Direction::West), Direction8::West);
+        assert_eq!(Direction8::NorthWest.turn_right(), Direction8::North);
+        assert_eq!(Direction8::SouthEast.reverse(), Direction8::NorthWest);
+        assert_eq!(
+            Direction8::ALL
+                .iter()
+                .map(|d| d.offset())
+                .fold(Point::ORIGIN, Add::add),
+            Point::ORIGIN
+        );
+    }
+
+    #[test]
+    fn test_point() {
+        let p = Point::new(2, 3);
+        assert_eq!(p + Direction::North, Point::new(2, 2));
+        assert_eq!(p.step(Direction8::SouthWest), Point::new(1, 4));
+        assert_eq!(p.manhattan(Point::new(-1, 5)), 5);
+        assert_eq!(Point::new(2u8, 3).manhattan(Point::new(4, 1)), 4);
+        assert_eq!(p.pos(), Some((3, 2)));
+        assert_eq!(Point::new(-1, 0).pos(), None);
+        assert_eq!(Point::from((3, 2)), p);
+        assert_eq!(p.neighbours4().count(), 4);
+    }
+}
@@ -0,0 +1,295 @@
+use crate::geom::Point;
+use crate::{PuzzleError, PuzzleResult};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// A position in a grid as `(row, column)`, counting from the top left.
+pub type Pos = (usize, usize);
+
+/// The offsets to the four orthogonal neighbours, clockwise from north.
+pub const OFFSETS_4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// The offsets to all eight neighbours, clockwise from north.
+pub const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+/// A rectangular grid stored row by row, sized by its input.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid of `width` by `height` cells, all set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// A grid from cells in row order.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> PuzzleResult<Self> {
+        if cells.len() != width * height {
+            return Err(PuzzleError::Input(format!(
+                "{} cells do not make a {width}x{height} grid",
+                cells.len()
+            )));
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parses one row per line, mapping every character to a cell. Blank
+    /// lines are skipped.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> PuzzleResult<Self> {
+        Self::try_parse(input, |c| Ok(f(c)))
+    }
+
+    /// Like [`Grid::parse`], for mappings that may reject a character.
+    pub fn try_parse(
+        input: &str,
+        mut f: impl FnMut(char) -> PuzzleResult<T>,
+    ) -> PuzzleResult<Self> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+
+        for line in input.lines().filter(|line| !line.is_empty()) {
+            let before = cells.len();
+            for c in line.chars() {
+                cells.push(f(c)?);
+            }
+
+            let row_width = cells.len() - before;
+            if *width.get_or_insert(row_width) != row_width {
+                return Err(PuzzleError::Input(format!(
+                    "Row {} is {row_width} wide, expected {}",
+                    height + 1,
+                    width.unwrap_or_default()
+                )));
+            }
+            height += 1;
+        }
+
+        Self::from_vec(width.unwrap_or_default(), height, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.cells[pos.0 * self.width + pos.1])
+    }
+
+    /// The cell at `point`, if it is inside the grid.
+    pub fn at(&self, point: Point) -> Option<&T> {
+        self.get(point.pos()?)
+    }
+
+    /// The position `(dr, dc)` away from `pos`, if it is inside the grid.
+    pub fn offset(&self, (row, col): Pos, (dr, dc): (isize, isize)) -> Option<Pos> {
+        let pos = (row.checked_add_signed(dr)?, col.checked_add_signed(dc)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// The orthogonal neighbours of `pos` inside the grid, clockwise from north.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS_4
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    /// All neighbours of `pos` inside the grid, clockwise from north.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS_8
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    /// The positions from `pos` in steps of `step` until the edge, starting
+    /// with `pos` itself.
+    pub fn ray(&self, pos: Pos, step: (isize, isize)) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.contains(pos).then_some(pos), move |&pos| {
+            self.offset(pos, step)
+        })
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.ray((0, col), (1, 0)).map(|pos| &self[pos])
+    }
+
+    /// The cells from `pos` down and to the right.
+    pub fn diagonal(&self, pos: Pos) -> impl Iterator<Item = &T> {
+        self.ray(pos, (1, 1)).map(|pos| &self[pos])
+    }
+
+    /// The cells from `pos` down and to the left.
+    pub fn anti_diagonal(&self, pos: Pos) -> impl Iterator<Item = &T> {
+        self.ray(pos, (1, -1)).map(|pos| &self[pos])
+    }
+
+    /// All positions in row order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i / width, i % width))
+    }
+
+    /// All cells with their positions, in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The position of the first cell, in row order, that matches.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find_map(|(pos, cell)| predicate(cell).then_some(pos))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.at(point)
+            .unwrap_or_else(|| panic!("{point} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        point
+            .pos()
+            .and_then(|pos| self.get_mut(pos))
+            .unwrap_or_else(|| panic!("{point} is outside the grid"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+abc
+def
+";
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse(SAMPLE, |c| c).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid[Point::new(2, 1)], 'f');
+        assert_eq!(grid.at(Point::new(-1, 0)), None);
+        assert_eq!(grid.to_string(), SAMPLE);
+
+        assert!(Grid::parse("ab\nc\n", |c| c).is_err());
+        assert!(Grid::try_parse("12\n3x\n", |c| c
+            .to_digit(10)
+            .ok_or_else(|| PuzzleError::Input(format!("Not a digit: {c}"))))
+        .is_err());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::parse(SAMPLE, |c| c).unwrap();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbours8((1, 1)).collect::<Vec<_>>(),
+            [(0, 1), (0, 2), (1, 2), (1, 0), (0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_views() {
+        let grid = Grid::parse(SAMPLE, |c| c).unwrap();
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(grid.diagonal((0, 0)).collect::<String>(), "ae");
+        assert_eq!(grid.anti_diagonal((0, 2)).collect::<String>(), "ce");
+        assert_eq!(grid.find(|&c| c == 'e'), Some((1, 1)));
+        assert_eq!(grid.map(|c| c.is_ascii_uppercase()).find(|&b| b), None);
+    }
+}
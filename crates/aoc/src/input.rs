@@ -1,80 +1,172 @@
 use crate::cache::AocCache;
+use crate::parse;
 use crate::{Day, PuzzleError, PuzzleResult, Year};
+use memmap2::Mmap;
 use std::fs::File;
-use std::io::{BufRead, BufReader, Read};
-use std::path::PathBuf;
+use std::io;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::str;
+use std::sync::OnceLock;
 
 #[derive(Clone)]
 enum Source<'a> {
     File(PathBuf),
-    #[allow(dead_code)]
     String(&'a str),
 }
 
 pub struct Input<'a> {
     implementation: Source<'a>,
+    /// The mapped file, once [`Input::bytes`] has been called on a file input.
+    mapped: OnceLock<Mmap>,
 }
 
 impl<'a> Input<'a> {
     pub fn from_path(path: PathBuf) -> Self {
         Input {
             implementation: Source::File(path),
+            mapped: OnceLock::new(),
         }
     }
 
+    /// The `n`th sample from the puzzle page, counting from 1, fetched into the cache if needed.
+    pub fn sample(year: u16, day: u8, n: usize) -> PuzzleResult<Self> {
+        let path = AocCache::default().get_sample_path(year, day, n)?;
+        Ok(Self::from_path(path))
+    }
+
     pub fn lines(&self) -> PuzzleResult<Lines<'a>> {
+        Ok(Lines {
+            lines: self.try_lines()?,
+        })
+    }
+
+    /// The lines of the input, with read errors and invalid UTF-8 reported
+    /// per line instead of panicking.
+    pub fn try_lines(&self) -> PuzzleResult<TryLines<'a>> {
         match &self.implementation {
-            Source::File(file) => Ok(Lines::from_file(File::open(file).map_err(|error| {
-                PuzzleError::IO {
-                    msg: format!("Failed to open file: {}", file.display()),
-                    error,
+            Source::File(path) => Ok(TryLines::from_file(open(path)?)),
+            Source::String(string) => Ok(TryLines::from_string(string)),
+        }
+    }
+
+    /// Calls `f` with every line of the input, reusing a single buffer rather
+    /// than allocating a `String` per line. Stops at the first error.
+    pub fn for_each_line<F>(&self, mut f: F) -> PuzzleResult<()>
+    where
+        F: FnMut(&str) -> PuzzleResult<()>,
+    {
+        match &self.implementation {
+            Source::File(path) => {
+                let mut reader = BufReader::new(open(path)?);
+                let mut buffer = String::new();
+                for number in 1.. {
+                    buffer.clear();
+                    if reader
+                        .read_line(&mut buffer)
+                        .map_err(|error| read_error(number, error))?
+                        == 0
+                    {
+                        break;
+                    }
+                    f(trim_newline(&buffer))?;
                 }
-            })?)),
-            Source::String(string) => Ok(Lines::from_string(string)),
+                Ok(())
+            }
+            Source::String(string) => string.lines().try_for_each(f),
         }
     }
 
     pub fn read_to_string(&self) -> PuzzleResult<String> {
+        Ok(self.text()?.to_string())
+    }
+
+    /// The raw contents of the input, without copying.
+    ///
+    /// Files are memory-mapped on first use and the mapping is shared by every
+    /// later call, so parsing from this never reads the file twice.
+    pub fn bytes(&self) -> PuzzleResult<&[u8]> {
         match &self.implementation {
             Source::File(path) => {
-                let mut reader = BufReader::new(File::open(path)?);
-                let mut buffer = String::new();
-                reader.read_to_string(&mut buffer)?;
-                Ok(buffer)
+                if let Some(mapped) = self.mapped.get() {
+                    return Ok(mapped);
+                }
+
+                let file = open(path)?;
+                // SAFETY: The cache writes every file, inputs and samples
+                // included, with `write_atomic`, which renames a new file over
+                // the old one. A mapped file is never modified in place, unless
+                // it is edited by hand while it is being solved.
+                let mapped = unsafe { Mmap::map(&file) }.map_err(|error| PuzzleError::IO {
+                    msg: format!("Failed to map file: {}", path.display()),
+                    error,
+                })?;
+                Ok(self.mapped.get_or_init(|| mapped))
             }
-            Source::String(string) => Ok(string.to_string()),
+            Source::String(string) => Ok(string.as_bytes()),
         }
     }
+
+    /// The blocks of lines between blank lines, without copying.
+    pub fn sections(&self) -> PuzzleResult<Vec<&str>> {
+        Ok(parse::sections(self.text()?))
+    }
+
+    /// The contents of the input as text, without copying.
+    pub fn text(&self) -> PuzzleResult<&str> {
+        str::from_utf8(self.bytes()?).map_err(|error| {
+            PuzzleError::Input(format!(
+                "Input is not valid UTF-8 after {} bytes",
+                error.valid_up_to()
+            ))
+        })
+    }
 }
 
 impl<'a> From<&'a str> for Input<'a> {
     fn from(string: &'a str) -> Self {
         Input {
             implementation: Source::String(string),
+            mapped: OnceLock::new(),
         }
     }
 }
 
-pub struct Lines<'a> {
-    implementation: LinesIteratorImpl<'a>,
+fn open(path: &Path) -> PuzzleResult<File> {
+    File::open(path).map_err(|error| PuzzleError::IO {
+        msg: format!("Failed to open file: {}", path.display()),
+        error,
+    })
 }
 
-enum LinesIteratorImpl<'a> {
-    File(std::io::Lines<BufReader<File>>),
-    String(core::str::Lines<'a>),
+fn read_error(number: usize, error: io::Error) -> PuzzleError {
+    PuzzleError::IO {
+        msg: format!("Failed to read line {number}"),
+        error,
+    }
+}
+
+fn trim_newline(line: &str) -> &str {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+/// The lines of an input, panicking on read errors. Use [`TryLines`] where
+/// the input may not be valid.
+pub struct Lines<'a> {
+    lines: TryLines<'a>,
 }
 
 impl<'a> Lines<'a> {
     pub fn from_file(file: File) -> Self {
         Lines {
-            implementation: LinesIteratorImpl::File(BufReader::new(file).lines()),
+            lines: TryLines::from_file(file),
         }
     }
 
     pub fn from_string(string: &'a str) -> Self {
-        let lines = string.lines();
         Lines {
-            implementation: LinesIteratorImpl::String(lines),
+            lines: TryLines::from_string(string),
         }
     }
 }
@@ -89,20 +181,131 @@ impl Iterator for Lines<'_> {
     type Item = String;
 
     fn next(&mut self) -> Option<Self::Item> {
+        self.lines
+            .next()
+            .map(|line| line.unwrap_or_else(|e| panic!("{e}")))
+    }
+}
+
+/// The lines of an input, each one an error if it can't be read.
+pub struct TryLines<'a> {
+    implementation: LinesIteratorImpl<'a>,
+    number: usize,
+}
+
+enum LinesIteratorImpl<'a> {
+    File(std::io::Lines<BufReader<File>>),
+    String(core::str::Lines<'a>),
+}
+
+impl<'a> TryLines<'a> {
+    pub fn from_file(file: File) -> Self {
+        TryLines {
+            implementation: LinesIteratorImpl::File(BufReader::new(file).lines()),
+            number: 0,
+        }
+    }
+
+    pub fn from_string(string: &'a str) -> Self {
+        TryLines {
+            implementation: LinesIteratorImpl::String(string.lines()),
+            number: 0,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for TryLines<'a> {
+    fn from(string: &'a str) -> Self {
+        TryLines::from_string(string)
+    }
+}
+
+impl Iterator for TryLines<'_> {
+    type Item = PuzzleResult<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.number += 1;
         match &mut self.implementation {
-            LinesIteratorImpl::File(lines) => lines.next().map(|line| line.unwrap()),
-            LinesIteratorImpl::String(iter) => iter.next().map(|line| line.to_string()),
+            LinesIteratorImpl::File(lines) => lines
+                .next()
+                .map(|line| line.map_err(|error| read_error(self.number, error))),
+            LinesIteratorImpl::String(iter) => iter.next().map(|line| Ok(line.to_string())),
         }
     }
 }
 
 pub trait InputFetcher {
-    fn get_input(&self, year: Year, day: Day) -> PuzzleResult<Input>;
+    fn get_input(&self, year: Year, day: Day) -> PuzzleResult<Input<'_>>;
 }
 
 impl InputFetcher for AocCache {
-    fn get_input(&self, year: Year, day: Day) -> PuzzleResult<Input> {
+    fn get_input(&self, year: Year, day: Day) -> PuzzleResult<Input<'_>> {
         let path = self.get_path(year.0, day.0)?;
         Ok(Input::from_path(path))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_try_lines_invalid_utf8() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("input.txt");
+        fs::write(&path, b"one\ntw\xffo\nthree\n").unwrap();
+
+        let lines: Vec<_> = Input::from_path(path).try_lines().unwrap().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].as_ref().unwrap(), "one");
+        match &lines[1] {
+            Err(PuzzleError::IO { msg, .. }) => assert_eq!(msg, "Failed to read line 2"),
+            line => panic!("Unexpected {line:?}"),
+        }
+        assert_eq!(lines[2].as_ref().unwrap(), "three");
+    }
+
+    #[test]
+    fn test_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("input.txt");
+        fs::write(&path, "1 2\n3 4\n").unwrap();
+
+        let input = Input::from_path(path.clone());
+        let bytes = input.bytes().unwrap();
+        assert_eq!(bytes, b"1 2\n3 4\n");
+        assert_eq!(input.bytes().unwrap().as_ptr(), bytes.as_ptr());
+        assert_eq!(input.text().unwrap(), "1 2\n3 4\n");
+
+        let string = "1 2\n";
+        assert_eq!(
+            Input::from(string).bytes().unwrap().as_ptr(),
+            string.as_ptr()
+        );
+
+        fs::write(&path, b"").unwrap();
+        assert_eq!(Input::from_path(path.clone()).bytes().unwrap(), b"");
+
+        fs::write(&path, b"\xff").unwrap();
+        assert!(Input::from_path(path).text().is_err());
+    }
+
+    #[test]
+    fn test_for_each_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("input.txt");
+        fs::write(&path, "one\r\ntwo\n\nfour").unwrap();
+
+        for input in [Input::from_path(path), Input::from("one\r\ntwo\n\nfour")] {
+            let mut lines = Vec::new();
+            input
+                .for_each_line(|line| {
+                    lines.push(line.to_string());
+                    Ok(())
+                })
+                .unwrap();
+            assert_eq!(lines, ["one", "two", "", "four"]);
+        }
+    }
+}
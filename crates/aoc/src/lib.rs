@@ -1,97 +1,69 @@
-use std::error::Error;
+use std::fmt;
 use std::fmt::{Display, Formatter};
-use std::time::Duration;
-use std::{fmt, io};
 
-pub use cache::AocCache;
-pub use input::{Input, InputFetcher, Lines};
+#[doc(hidden)]
+pub use inventory;
+
+pub use answers::{Answers, Verdict};
+pub use aoc_macros::solution;
+pub use bench::{bench, Bench, BenchOptions, Stats};
+pub use cache::{AocCache, CACHE_DIR_ENV, DEFAULT_BASE_URL};
+pub use calendar::{Clock, SystemClock};
+pub use cli::{Cli, Selection};
+pub use download::Retry;
+pub use error::{PuzzleError, PuzzleResult};
+pub use geom::{Direction, Direction8, Point};
+pub use grid::Grid;
+pub use input::{Input, InputFetcher, Lines, TryLines};
+pub use page::PuzzlePage;
+pub use registry::{Registered, Registry};
+pub use runner::{run, RunOptions, Status, Summary, Timing};
+pub use solution::{Answer, Part, PartResult, Progress, Puzzle, Skip, Solution, Solved, Tag};
+pub use submit::{submit, Outcome};
+
+pub mod answers;
+
+pub mod bench;
+
+pub mod download;
+
+pub mod error;
 
 pub mod input;
 
 pub mod cache;
 
-pub type PuzzleResult<T> = Result<T, PuzzleError>;
-type AoCSolution = fn(&AocCache) -> PuzzleResult<bool>;
-
-#[derive(Debug)]
-pub enum PuzzleError {
-    IO { msg: String, error: io::Error },
-    Input(String),
-    Verification(String),
-    Solution(String),
-    DownloadFailed(String, Box<dyn Error>),
-    Cache(String, Box<dyn Error>),
-}
+pub mod calendar;
 
-impl Error for PuzzleError {
-    fn source(&self) -> Option<&(dyn Error + 'static)> {
-        Some(self)
-    }
-}
+pub mod cli;
 
-impl Display for PuzzleError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
+pub mod geom;
 
-pub fn run<T>(seq: T) -> PuzzleResult<()>
-where
-    T: IntoIterator<Item = AoCSolution>,
-{
-    #[cfg(feature = "OnlyLastPuzzle")]
-    {
-        if let Some(f) = seq.into_iter().last() {
-            verify(f)?;
-            Ok(())
-        } else {
-            Err(PuzzleError::Solution("No puzzles available".into()))
-        }
-    }
+pub mod grid;
 
-    #[cfg(not(feature = "OnlyLastPuzzle"))]
-    {
-        for f in seq {
-            verify(f)?;
-        }
+pub mod page;
 
-        Ok(())
-    }
-}
+pub mod parse;
 
-fn verify(f: AoCSolution) -> PuzzleResult<()> {
-    let cache = AocCache::default();
+pub mod registry;
 
-    let start = std::time::Instant::now();
+pub mod runner;
 
-    let result = match f(&cache) {
-        Ok(false) => Err(PuzzleError::Verification("Verification failed".to_string())),
-        Err(err) => Err(PuzzleError::Solution(format!(
-            "Execution failed: {:?}",
-            err
-        ))),
-        _ => Ok(()),
-    };
+pub mod scaffold;
 
-    let duration = start.elapsed();
-    println!(
-        "Duration: {:?}",
-        Duration::from_millis(duration.as_millis() as u64)
-    );
+pub mod search;
 
-    result
-}
+pub mod session;
 
-impl From<io::Error> for PuzzleError {
-    fn from(error: io::Error) -> Self {
-        PuzzleError::IO {
-            msg: "IO error occurred".to_string(),
-            error,
-        }
-    }
-}
+pub mod sim;
 
-#[derive(Debug)]
+pub mod solution;
+
+pub mod submit;
+
+pub mod viz;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Year(pub u16);
 
 impl Display for Year {
@@ -100,7 +72,7 @@ impl Display for Year {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Day(pub u8);
 
 impl Display for Day {
@@ -111,7 +83,11 @@ impl Display for Day {
 
 pub fn head(year: Year, day: Day, title: &str) {
     println!();
-    println!("-- Advent of Code {} Day {}: {} ---", year.0, day.0, title)
+    println!("{}", heading(year, day, title))
+}
+
+pub fn heading(year: Year, day: Day, title: &str) -> String {
+    format!("-- Advent of Code {} Day {}: {} ---", year.0, day.0, title)
 }
 
 #[allow(dead_code)]
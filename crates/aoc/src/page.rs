@@ -0,0 +1,97 @@
+use scraper::{Html, Selector};
+
+/// A puzzle description page, as served at `/<year>/day/<day>`.
+///
+/// The page has one `<article>` per unlocked part, so the second part and its
+/// samples only show up once the first part is solved.
+pub struct PuzzlePage {
+    html: Html,
+}
+
+impl PuzzlePage {
+    pub fn parse(html: &str) -> Self {
+        Self {
+            html: Html::parse_document(html),
+        }
+    }
+
+    /// The puzzle title, from the `--- Day 6: Guard Gallivant ---` heading.
+    pub fn title(&self) -> Option<String> {
+        let heading: String = self
+            .html
+            .select(&selector("article.day-desc h2"))
+            .next()?
+            .text()
+            .collect();
+        let title = heading.trim().trim_matches('-').trim();
+        let (_, title) = title.split_once(": ")?;
+        Some(title.to_string())
+    }
+
+    /// The number of parts described on the page.
+    pub fn parts(&self) -> usize {
+        self.html.select(&selector("article.day-desc")).count()
+    }
+
+    /// The puzzle descriptions rendered as Markdown.
+    pub fn markdown(&self) -> String {
+        self.html
+            .select(&selector("article.day-desc"))
+            .map(|article| {
+                html2md::parse_html(&article.inner_html())
+                    .trim()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// The contents of the `<pre><code>` blocks, in order of appearance.
+    pub fn samples(&self) -> Vec<String> {
+        self.html
+            .select(&selector("article.day-desc pre > code"))
+            .map(|code| code.text().collect())
+            .collect()
+    }
+}
+
+fn selector(selectors: &str) -> Selector {
+    Selector::parse(selectors).expect("Invalid selector")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2>
+<p>For example:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<p>In the example list above, the total is <code><em>11</em></code>!</p>
+</article>
+<p>Your puzzle answer was <code>2430334</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>a &lt; b
+</code></pre>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn test_samples() {
+        let page = PuzzlePage::parse(PAGE);
+        assert_eq!(page.parts(), 2);
+        assert_eq!(page.title().as_deref(), Some("Historian Hysteria"));
+        assert_eq!(page.samples(), vec!["3   4\n4   3\n", "a < b\n"]);
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = PuzzlePage::parse(PAGE).markdown();
+        assert!(markdown.contains("Day 1: Historian Hysteria"));
+        assert!(markdown.contains("```\n3   4\n4   3\n"));
+        assert!(markdown.contains("Part Two"));
+        assert!(!markdown.contains("2430334"));
+    }
+}
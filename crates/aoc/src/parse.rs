@@ -0,0 +1,228 @@
+use crate::{PuzzleError, PuzzleResult};
+use std::any::type_name;
+use std::str::FromStr;
+
+/// A mismatch at byte `offset` of `text`, reported with its line and column.
+fn error(text: &[u8], offset: usize, expected: &str) -> PuzzleError {
+    let before = &text[..offset];
+    let start = before
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+    let end = text[offset..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(text.len(), |i| offset + i);
+
+    PuzzleError::Parse {
+        year: None,
+        day: None,
+        line: before.iter().filter(|&&b| b == b'\n').count() + 1,
+        col: String::from_utf8_lossy(&text[start..offset])
+            .chars()
+            .count()
+            + 1,
+        snippet: String::from_utf8_lossy(&text[start..end])
+            .trim_end_matches('\r')
+            .to_string(),
+        expected: expected.to_string(),
+    }
+}
+
+/// All integers in `text`, in order. A `-` directly before a number makes it
+/// negative unless it follows a digit, as in a range like `3-7`.
+pub fn ints(text: &(impl AsRef<[u8]> + ?Sized)) -> PuzzleResult<Vec<i64>> {
+    let text = text.as_ref();
+    let mut ints = Vec::new();
+    let mut i = 0;
+
+    while i < text.len() {
+        if !text[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let negative = i > 0 && text[i - 1] == b'-' && (i < 2 || !text[i - 2].is_ascii_digit());
+        let start = if negative { i - 1 } else { i };
+
+        let mut value: i64 = 0;
+        while i < text.len() && text[i].is_ascii_digit() {
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add((text[i] - b'0') as i64))
+                .ok_or_else(|| error(text, start, "a 64 bit integer"))?;
+            i += 1;
+        }
+
+        ints.push(if negative { -value } else { value });
+    }
+
+    Ok(ints)
+}
+
+/// Exactly `N` integers from `text`, e.g. the coordinates in `p=0,4 v=3,-3`.
+pub fn ints_n<const N: usize>(text: &(impl AsRef<[u8]> + ?Sized)) -> PuzzleResult<[i64; N]> {
+    let bytes = text.as_ref();
+    let ints = ints(bytes)?;
+    let count = ints.len();
+    ints.try_into()
+        .map_err(|_| error(bytes, 0, &format!("{N} integers but there are {count}")))
+}
+
+/// Parses every non-blank line of `text` with `f`, moving parse errors to
+/// the line they were found on.
+pub fn lines<T>(text: &str, mut f: impl FnMut(&str) -> PuzzleResult<T>) -> PuzzleResult<Vec<T>> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| f(line).map_err(|error| error.on_line(i + 1)))
+        .collect()
+}
+
+/// The blocks of lines between blank lines.
+pub fn sections(text: &str) -> Vec<&str> {
+    let mut sections = Vec::new();
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        if line.trim_end_matches(['\r', '\n']).is_empty() {
+            if let Some(start) = start.take() {
+                sections.push(&text[start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.trim_end_matches(['\r', '\n']).len();
+        }
+        offset += line.len();
+    }
+
+    if let Some(start) = start {
+        sections.push(&text[start..end]);
+    }
+
+    sections
+}
+
+/// The fields matched by [`scan`], each parsed on demand.
+#[derive(Clone, Debug)]
+pub struct Captures<'a> {
+    line: &'a str,
+    fields: Vec<(usize, &'a str)>,
+}
+
+impl<'a> Captures<'a> {
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// The `i`th field as text.
+    pub fn str(&self, i: usize) -> &'a str {
+        self.fields[i].1
+    }
+
+    /// The `i`th field parsed as a `T`.
+    pub fn get<T: FromStr>(&self, i: usize) -> PuzzleResult<T> {
+        let (offset, field) = self.fields[i];
+        field
+            .parse()
+            .map_err(|_| error(self.line.as_bytes(), offset, type_name::<T>()))
+    }
+}
+
+/// Matches `line` against a pattern where each `{}` captures the text up to
+/// the literal text that follows it, or up to the end of the line.
+///
+/// `scan("p={},{} v={},{}", "p=0,4 v=3,-3")` captures `0`, `4`, `3` and `-3`.
+pub fn scan<'a>(pattern: &str, line: &'a str) -> PuzzleResult<Captures<'a>> {
+    let mut literals = pattern.split("{}");
+    let mut fields = Vec::new();
+    let mut offset = 0;
+
+    let first = literals.next().unwrap_or_default();
+    if !line.starts_with(first) {
+        return Err(error(line.as_bytes(), 0, &format!("'{first}'")));
+    }
+    offset += first.len();
+
+    for literal in literals {
+        let rest = &line[offset..];
+        let end = if literal.is_empty() {
+            rest.len()
+        } else {
+            rest.find(literal).ok_or_else(|| {
+                error(line.as_bytes(), offset, &format!("a field and '{literal}'"))
+            })?
+        };
+
+        if end == 0 {
+            return Err(error(line.as_bytes(), offset, "a field"));
+        }
+
+        fields.push((offset, &rest[..end]));
+        offset += end + literal.len();
+    }
+
+    if offset != line.len() {
+        return Err(error(line.as_bytes(), offset, "the end of the line"));
+    }
+
+    Ok(Captures { line, fields })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ints() {
+        assert_eq!(ints("p=0,4 v=3,-3").unwrap(), [0, 4, 3, -3]);
+        assert_eq!(ints(b"Button A: X+94, Y+34").unwrap(), [94, 34]);
+        assert_eq!(ints("1-3 a: -12").unwrap(), [1, 3, -12]);
+        assert_eq!(ints_n::<2>("x=-1, y=2").unwrap(), [-1, 2]);
+
+        let error = ints_n::<3>("x=-1, y=2").unwrap_err().to_string();
+        assert!(error.contains("3 integers but there are 2"), "{error}");
+        let error = ints("1\n  99999999999999999999").unwrap_err().to_string();
+        assert!(error.contains("Line 2, column 3"), "{error}");
+    }
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(lines("1\n\n2\n", ints_n::<1>).unwrap(), [[1], [2]]);
+
+        let error = lines("1\n2\nx\n", ints_n::<1>).unwrap_err();
+        assert!(
+            matches!(error, PuzzleError::Parse { line: 3, .. }),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_sections() {
+        assert_eq!(sections("a\nb\n\n\nc\r\n\r\nd\n"), ["a\nb", "c", "d"]);
+        assert_eq!(sections("\n"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_scan() {
+        let captures = scan("p={},{} v={},{}", "p=0,4 v=3,-3").unwrap();
+        assert_eq!(captures.len(), 4);
+        assert_eq!(captures.get::<i32>(3).unwrap(), -3);
+        assert_eq!(captures.str(0), "0");
+
+        let captures = scan("{} to {} = {}", "London to Dublin = x").unwrap();
+        assert_eq!(captures.str(1), "Dublin");
+        let error = captures.get::<u32>(2).unwrap_err().to_string();
+        assert_eq!(error, "Line 1, column 20: expected u32");
+
+        let error = scan("{} = {}", "London to Dublin").unwrap_err().to_string();
+        assert_eq!(error, "Line 1, column 1: expected a field and ' = '");
+        assert!(scan("a{}", "b1").is_err());
+    }
+}
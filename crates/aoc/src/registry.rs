@@ -0,0 +1,148 @@
+use crate::{Puzzle, PuzzleError, PuzzleResult, Selection};
+
+/// A solution registered by [`#[aoc::solution]`](crate::solution).
+pub struct Registered(pub &'static dyn Puzzle);
+
+inventory::collect!(Registered);
+
+/// Every registered puzzle, ordered by year and day.
+#[derive(Default)]
+pub struct Registry {
+    puzzles: Vec<&'static dyn Puzzle>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every solution registered by [`#[aoc::solution]`](crate::solution)
+    /// in the crates linked into the binary.
+    pub fn registered() -> Self {
+        let puzzles: Vec<_> = inventory::iter::<Registered>
+            .into_iter()
+            .map(|registered| registered.0)
+            .collect();
+        Self::new().with(&puzzles)
+    }
+
+    pub fn register(&mut self, puzzles: &[&'static dyn Puzzle]) {
+        self.puzzles.extend_from_slice(puzzles);
+        self.puzzles.sort_by_key(|p| (p.year().0, p.day().0));
+    }
+
+    pub fn with(mut self, puzzles: &[&'static dyn Puzzle]) -> Self {
+        self.register(puzzles);
+        self
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'static dyn Puzzle> + '_ {
+        self.puzzles.iter().copied()
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Option<&'static dyn Puzzle> {
+        self.iter().find(|p| p.year().0 == year && p.day().0 == day)
+    }
+
+    pub fn years(&self) -> Vec<u16> {
+        let mut years: Vec<_> = self.iter().map(|p| p.year().0).collect();
+        years.dedup();
+        years
+    }
+
+    pub fn days(&self, year: u16) -> Vec<u8> {
+        self.iter()
+            .filter(|p| p.year().0 == year)
+            .map(|p| p.day().0)
+            .collect()
+    }
+
+    /// The puzzles matching `selection`, in order.
+    pub fn select(&self, selection: &Selection) -> PuzzleResult<Vec<&'static dyn Puzzle>> {
+        let years = self.years();
+        if let Some(missing) = selection.years().find(|y| !years.contains(y)) {
+            return Err(PuzzleError::Input(format!("No puzzles for {missing}")));
+        }
+
+        let puzzles: Vec<_> = years
+            .into_iter()
+            .flat_map(|year| {
+                selection
+                    .days(year, &self.days(year))
+                    .into_iter()
+                    .filter_map(move |day| self.get(year, day))
+            })
+            .collect();
+
+        if puzzles.is_empty() {
+            return Err(PuzzleError::Input("No puzzles selected".into()));
+        }
+
+        Ok(puzzles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Answer, Day, Input, Solution, Year};
+
+    struct Stub(u16, u8);
+
+    impl Solution for Stub {
+        type Parsed<'a> = ();
+
+        fn year(&self) -> Year {
+            Year(self.0)
+        }
+
+        fn day(&self) -> Day {
+            Day(self.1)
+        }
+
+        fn title(&self) -> &'static str {
+            "Stub"
+        }
+
+        fn parse(&self, _input: &Input) -> PuzzleResult<()> {
+            Ok(())
+        }
+
+        fn part1(&self, _parsed: &()) -> PuzzleResult<Answer> {
+            Ok(0.into())
+        }
+
+        fn part2(&self, _parsed: &()) -> PuzzleResult<Answer> {
+            Ok(0.into())
+        }
+    }
+
+    static PUZZLES_24: &[&dyn Puzzle] = &[&Stub(2024, 2), &Stub(2024, 1)];
+    static PUZZLES_15: &[&dyn Puzzle] = &[&Stub(2015, 1), &Stub(2015, 7)];
+
+    fn ids(puzzles: &[&dyn Puzzle]) -> Vec<(u16, u8)> {
+        puzzles.iter().map(|p| (p.year().0, p.day().0)).collect()
+    }
+
+    #[test]
+    fn test_select() {
+        let registry = Registry::new().with(PUZZLES_24).with(PUZZLES_15);
+        assert_eq!(registry.years(), vec![2015, 2024]);
+        assert_eq!(registry.days(2024), vec![1, 2]);
+
+        let all = registry.select(&Selection::default()).unwrap();
+        assert_eq!(ids(&all), vec![(2015, 1), (2015, 7), (2024, 1), (2024, 2)]);
+
+        let latest = registry
+            .select(&Selection::parse(&["2015", "latest", "2024", "1"]).unwrap())
+            .unwrap();
+        assert_eq!(ids(&latest), vec![(2015, 7), (2024, 1)]);
+
+        assert!(registry
+            .select(&Selection::parse(&["2016"]).unwrap())
+            .is_err());
+        assert!(registry
+            .select(&Selection::parse(&["2024", "3"]).unwrap())
+            .is_err());
+    }
+}
@@ -0,0 +1,754 @@
+use crate::viz::{Format, Frame};
+use crate::{heading, AocCache, Day, Input, Part, PartResult, Progress, Puzzle, PuzzleError};
+use crate::{viz, PuzzleResult, Registry, Selection, Skip, Tag, Verdict, Year};
+use std::any::Any;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::Once;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How the selected puzzles are run.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Save answers for parts that have no recorded answer yet.
+    pub record: bool,
+    /// Give up on a puzzle that takes longer than this. Its thread is left
+    /// running in the background until the run ends.
+    pub timeout: Option<Duration>,
+    /// How many puzzles to solve at the same time. Puzzles that use rayon
+    /// share its global pool, so this doesn't multiply its threads.
+    pub jobs: usize,
+    /// Leave out parts with these tags.
+    pub skip: Skip,
+    /// Record the frames that the solutions draw, and play them after the
+    /// output of their puzzle or save them in the cache.
+    pub viz: Option<Format>,
+}
+
+/// What became of one part of a puzzle.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Status {
+    Answered(Verdict),
+    Failed(String),
+    Panicked(String),
+    TimedOut,
+    /// Left out because of its tag.
+    Skipped(Tag),
+}
+
+impl Status {
+    /// Whether the part gave an answer that isn't known to be wrong.
+    pub fn passed(&self) -> bool {
+        matches!(
+            self,
+            Status::Answered(Verdict::Correct) | Status::Answered(Verdict::Unknown)
+        )
+    }
+
+    /// Whether the part was run and didn't pass.
+    pub fn failed(&self) -> bool {
+        !self.passed() && !matches!(self, Status::Skipped(_))
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Answered(verdict) => write!(f, "{}", verdict),
+            Status::Failed(msg) => write!(f, "failed: {}", msg),
+            Status::Panicked(msg) => write!(f, "panicked: {}", msg),
+            Status::TimedOut => write!(f, "timed out"),
+            Status::Skipped(tag) => write!(f, "skipped ({})", tag),
+        }
+    }
+}
+
+/// Appends a line to the buffered output of a puzzle.
+macro_rules! say {
+    ($out:expr, $($arg:tt)*) => {{
+        $out.push_str(&format!($($arg)*));
+        $out.push('\n');
+    }};
+}
+
+thread_local! {
+    /// The puzzle that this thread works on in a run.
+    static PUZZLE: Cell<Option<(Year, Day)>> = const { Cell::new(None) };
+    /// Whether this thread solves a puzzle from [`solve_isolated`], whose
+    /// panics are reported with its output instead of by the panic hook.
+    static ISOLATED: Cell<bool> = const { Cell::new(false) };
+    /// Where the solution on this thread panicked.
+    static PANIC_LOCATION: Cell<Option<String>> = const { Cell::new(None) };
+}
+
+/// Tells what is going on while a puzzle is being fetched, e.g. downloads,
+/// retries and waits, as it happens. It goes to stderr, one line at a
+/// time, and during a run it names the puzzle, since the output of the
+/// puzzles is only printed once they are done.
+pub fn progress(msg: impl Display) {
+    match PUZZLE.get() {
+        Some((year, day)) => eprintln!("{year} day {day}: {msg}"),
+        None => eprintln!("{msg}"),
+    }
+}
+
+/// Runs the selected puzzles, each in its own thread, up to
+/// [`RunOptions::jobs`] at a time.
+///
+/// Each answer is checked against the ones recorded in the cache. Wrong
+/// answers, errors, panics and timeouts are reported, and the run goes on
+/// with the next part or puzzle. The output of each puzzle is printed in
+/// order once it is done, followed by its frames when they are played.
+pub fn run(
+    registry: &Registry,
+    cache: &AocCache,
+    selection: &Selection,
+    options: &RunOptions,
+) -> PuzzleResult<Summary> {
+    println!("Using cache {}", cache.root().display());
+
+    let puzzles = registry.select(selection)?;
+    let start = Instant::now();
+    let cpu_start = cpu_time();
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    let mut timings: Vec<Option<Timing>> = puzzles.iter().map(|_| None).collect();
+    let mut outputs: Vec<Option<(String, Vec<Frame>)>> = puzzles.iter().map(|_| None).collect();
+
+    thread::scope(|scope| {
+        for _ in 0..options.jobs.min(puzzles.len()).max(1) {
+            let sender = sender.clone();
+            let (next, puzzles) = (&next, &puzzles);
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&puzzle) = puzzles.get(i) else {
+                    break;
+                };
+                let verified = verify(cache, puzzle, selection.part, options);
+                if sender.send((i, verified)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut printed = 0;
+        for (i, (timing, output, frames)) in receiver {
+            timings[i] = Some(timing);
+            outputs[i] = Some((output, frames));
+            while let Some((output, frames)) = outputs.get_mut(printed).and_then(Option::take) {
+                print!("{}", output);
+                if let Err(error) = viz::play(&frames, viz::FRAME_DELAY, &mut io::stdout()) {
+                    eprintln!("Can't play the frames: {error}");
+                }
+                printed += 1;
+            }
+        }
+    });
+
+    Ok(Summary {
+        timings: timings.into_iter().flatten().collect(),
+        elapsed: start.elapsed(),
+        cpu: cpu_start
+            .zip(cpu_time())
+            .map(|(start, end)| end.saturating_sub(start)),
+    })
+}
+
+fn verify(
+    cache: &AocCache,
+    puzzle: &'static dyn Puzzle,
+    part: Option<Part>,
+    options: &RunOptions,
+) -> (Timing, String, Vec<Frame>) {
+    PUZZLE.set(Some((puzzle.year(), puzzle.day())));
+    let mut out = String::new();
+    let mut frames = Vec::new();
+    say!(
+        out,
+        "\n{}",
+        heading(puzzle.year(), puzzle.day(), puzzle.title())
+    );
+
+    let mut parts = Vec::new();
+    let mut skipped = Vec::new();
+    for p in [Part::One, Part::Two] {
+        if part.is_some_and(|selected| selected != p) {
+            continue;
+        }
+        match options.skip.reason(puzzle, p) {
+            Some(tag) => skipped.push((p, Status::Skipped(tag))),
+            None => parts.push(p),
+        }
+    }
+
+    let start = Instant::now();
+    let mut statuses = match options.skip.select(puzzle, part) {
+        Some(selected) => check(
+            cache,
+            puzzle,
+            selected,
+            &parts,
+            options,
+            &mut out,
+            &mut frames,
+        )
+        .unwrap_or_else(|error| {
+            say!(out, "{}", error.pretty());
+            let status = Status::Failed(error.to_string());
+            parts.iter().map(|&p| (p, status.clone())).collect()
+        }),
+        None => Vec::new(),
+    };
+    let time = start.elapsed();
+
+    for (p, status) in skipped {
+        say!(out, "Part {}: {}", p, status);
+        statuses.push((p, status));
+    }
+    statuses.sort_by_key(|&(p, _)| p);
+
+    say!(out, "Duration: {:.1?}", time);
+
+    let timing = Timing {
+        year: puzzle.year(),
+        day: puzzle.day(),
+        title: puzzle.title(),
+        time,
+        statuses,
+    };
+    PUZZLE.set(None);
+    (timing, out, frames)
+}
+
+/// Solves the puzzle and checks the answers. A part that fails is
+/// reported, and the parts after it are solved on their own. Recorded
+/// frames are saved, or added to `frames` to be played.
+fn check(
+    cache: &AocCache,
+    puzzle: &'static dyn Puzzle,
+    part: Option<Part>,
+    parts: &[Part],
+    options: &RunOptions,
+    out: &mut String,
+    frames: &mut Vec<Frame>,
+) -> PuzzleResult<Vec<(Part, Status)>> {
+    let (year, day) = (puzzle.year().0, puzzle.day().0);
+    let path = cache.get_path(year, day)?;
+    let mut answers = cache.answers(year, day)?;
+
+    let mut statuses = Vec::new();
+    let mut pending: VecDeque<Part> = parts.iter().copied().collect();
+    let mut selected = part;
+    let mut recorded = false;
+
+    loop {
+        let attempt = solve_isolated(
+            puzzle,
+            path.clone(),
+            selected,
+            options.timeout,
+            options.viz.is_some(),
+            out,
+        );
+
+        for (p, recorded_frames) in &attempt.parts {
+            let verdict = answers.check(p.part, &p.answer);
+            say!(out, "Part {}: {} ({})", p.part, p.answer, verdict);
+
+            match options.viz {
+                _ if recorded_frames.is_empty() => {}
+                Some(Format::Terminal) => frames.extend_from_slice(recorded_frames),
+                Some(format) => {
+                    let base = cache.viz_path(year, day, p.part);
+                    match viz::export(recorded_frames, format, &base) {
+                        Ok(path) => say!(
+                            out,
+                            "Part {}: {} frames saved to {}",
+                            p.part,
+                            recorded_frames.len(),
+                            path.display()
+                        ),
+                        Err(error) => say!(out, "Part {}: {}", p.part, error),
+                    }
+                }
+                None => {}
+            }
+
+            if options.record && verdict == Verdict::Unknown {
+                answers.set(p.part, p.answer.clone());
+                recorded = true;
+            }
+            pending.retain(|&q| q != p.part);
+            statuses.push((p.part, Status::Answered(verdict)));
+        }
+
+        let Some(failure) = attempt.failure else {
+            break;
+        };
+
+        if !attempt.parsed {
+            say!(out, "Parsing {}", failure);
+            statuses.extend(pending.drain(..).map(|p| (p, failure.clone())));
+            break;
+        }
+
+        if let Some(failed) = pending.pop_front() {
+            say!(out, "Part {}: {}", failed, failure);
+            statuses.push((failed, failure));
+        }
+        match pending.front() {
+            Some(&next) => selected = Some(next),
+            None => break,
+        }
+    }
+
+    if recorded {
+        cache.record_answers(year, day, &answers)?;
+        say!(out, "Recorded answers");
+    }
+
+    Ok(statuses)
+}
+
+/// How far a solution got before it finished or failed.
+#[derive(Debug, Default)]
+struct Attempt {
+    parsed: bool,
+    /// The solved parts, with the frames recorded while solving each.
+    parts: Vec<(PartResult, Vec<Frame>)>,
+    failure: Option<Status>,
+}
+
+enum Event {
+    Parsed,
+    Solved(PartResult, Vec<Frame>),
+    Done,
+    Failed(PuzzleError),
+    Panicked(String),
+}
+
+/// Solves the puzzle in a thread of its own, so that a panic or a timeout
+/// only ends this attempt. A panic is reported with where it happened as
+/// the failure, and not printed by the panic hook. With `record`, the
+/// frames drawn while solving a part come with its answer.
+fn solve_isolated(
+    puzzle: &'static dyn Puzzle,
+    path: PathBuf,
+    part: Option<Part>,
+    timeout: Option<Duration>,
+    record: bool,
+    out: &mut String,
+) -> Attempt {
+    install_panic_hook();
+    let (sender, receiver) = mpsc::channel();
+    let name = format!("{} day {}", puzzle.year(), puzzle.day());
+    let spawned = thread::Builder::new().name(name).spawn(move || {
+        ISOLATED.set(true);
+        let input = Input::from_path(path);
+        if record {
+            viz::start();
+        }
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            puzzle.solve_each(&input, part, &mut |progress| {
+                let _ = sender.send(match progress {
+                    Progress::Parsed(_) => Event::Parsed,
+                    Progress::Solved(result) => Event::Solved(result.clone(), viz::take()),
+                });
+            })
+        }));
+        let _ = sender.send(match result {
+            Ok(Ok(_)) => Event::Done,
+            Ok(Err(error)) => Event::Failed(error),
+            Err(payload) => Event::Panicked(match PANIC_LOCATION.take() {
+                Some(location) => format!("{} at {}", panic_message(payload), location),
+                None => panic_message(payload),
+            }),
+        });
+    });
+
+    let mut attempt = Attempt::default();
+    if let Err(error) = spawned {
+        attempt.failure = Some(Status::Failed(format!("Can't start a thread: {error}")));
+        return attempt;
+    }
+
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    loop {
+        let event = match deadline {
+            Some(deadline) => {
+                receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match event {
+            Ok(Event::Parsed) => attempt.parsed = true,
+            Ok(Event::Solved(result, frames)) => attempt.parts.push((result, frames)),
+            Ok(Event::Done) => break,
+            Ok(Event::Failed(error)) => {
+                if matches!(error, PuzzleError::Parse { .. }) {
+                    say!(out, "{}", error.pretty());
+                }
+                attempt.failure = Some(Status::Failed(error.to_string()));
+                break;
+            }
+            Ok(Event::Panicked(msg)) => {
+                attempt.failure = Some(Status::Panicked(msg));
+                break;
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                attempt.failure = Some(Status::TimedOut);
+                break;
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                attempt.failure = Some(Status::Panicked("the solution thread died".into()));
+                break;
+            }
+        }
+    }
+
+    attempt
+}
+
+/// The CPU time used by all threads of the process so far.
+#[cfg(unix)]
+fn cpu_time() -> Option<Duration> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+    // SAFETY: getrusage fills in the struct when it returns 0.
+    let usage = unsafe {
+        if libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) != 0 {
+            return None;
+        }
+        usage.assume_init()
+    };
+
+    let time = |t: libc::timeval| {
+        Duration::from_secs(t.tv_sec as u64) + Duration::from_micros(t.tv_usec as u64)
+    };
+    Some(time(usage.ru_utime) + time(usage.ru_stime))
+}
+
+#[cfg(not(unix))]
+fn cpu_time() -> Option<Duration> {
+    None
+}
+
+/// Keeps the location of panics in the threads of [`solve_isolated`] and
+/// leaves the others to the hook that was there.
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if ISOLATED.get() {
+                PANIC_LOCATION.set(info.location().map(ToString::to_string));
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|msg| msg.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// The time spent on one puzzle, including reading its input.
+#[derive(Debug)]
+pub struct Timing {
+    pub year: Year,
+    pub day: Day,
+    pub title: &'static str,
+    pub time: Duration,
+    pub statuses: Vec<(Part, Status)>,
+}
+
+/// The timings of every puzzle in a run, across all years.
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub timings: Vec<Timing>,
+    /// The wall time of the whole run.
+    pub elapsed: Duration,
+    /// The CPU time of the whole run, on all threads, where it is known.
+    pub cpu: Option<Duration>,
+}
+
+impl Summary {
+    /// The summed puzzle times per year, in order.
+    pub fn year_totals(&self) -> Vec<(Year, Duration)> {
+        let mut totals: Vec<(Year, Duration)> = Vec::new();
+        for timing in &self.timings {
+            match totals.last_mut() {
+                Some((year, total)) if *year == timing.year => *total += timing.time,
+                _ => totals.push((timing.year, timing.time)),
+            }
+        }
+        totals
+    }
+
+    /// The number of correct, wrong and unknown answers.
+    pub fn verdict_counts(&self) -> (usize, usize, usize) {
+        self.statuses().fold(
+            (0, 0, 0),
+            |(correct, wrong, unknown), (_, status)| match status {
+                Status::Answered(Verdict::Correct) => (correct + 1, wrong, unknown),
+                Status::Answered(Verdict::Wrong { .. }) => (correct, wrong + 1, unknown),
+                Status::Answered(Verdict::Unknown) => (correct, wrong, unknown + 1),
+                _ => (correct, wrong, unknown),
+            },
+        )
+    }
+
+    /// The number of parts that passed, failed, panicked, timed out and
+    /// were skipped, per part. A wrong answer counts as a failure.
+    pub fn outcome_counts(&self, part: Part) -> [usize; 5] {
+        let mut counts = [0; 5];
+        for (_, status) in self.statuses().filter(|(p, _)| *p == part) {
+            let i = match status {
+                status if status.passed() => 0,
+                Status::Answered(_) | Status::Failed(_) => 1,
+                Status::Panicked(_) => 2,
+                Status::TimedOut => 3,
+                Status::Skipped(_) => 4,
+            };
+            counts[i] += 1;
+        }
+        counts
+    }
+
+    /// Whether every part that was run passed.
+    pub fn passed(&self) -> bool {
+        !self.statuses().any(|(_, status)| status.failed())
+    }
+
+    fn statuses(&self) -> impl Iterator<Item = &(Part, Status)> {
+        self.timings.iter().flat_map(|t| &t.statuses)
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "-- Summary ---")?;
+        for t in &self.timings {
+            let statuses: Vec<_> = t
+                .statuses
+                .iter()
+                .map(|(part, status)| match status {
+                    Status::Answered(Verdict::Wrong { .. }) => format!("{}: wrong", part),
+                    Status::Failed(_) => format!("{}: failed", part),
+                    Status::Panicked(_) => format!("{}: panicked", part),
+                    status => format!("{}: {}", part, status),
+                })
+                .collect();
+            writeln!(
+                f,
+                "{} Day {:>2}: {:<40} {:>10.1?}  {}",
+                t.year.0,
+                t.day.0,
+                t.title,
+                t.time,
+                statuses.join(", ")
+            )?;
+        }
+
+        let totals = self.year_totals();
+        if totals.len() > 1 {
+            writeln!(f)?;
+            for (year, total) in totals {
+                writeln!(f, "{:<52} {:>10.1?}", year.0, total)?;
+            }
+        }
+
+        writeln!(
+            f,
+            "\n{:<8} {:>6} {:>6} {:>6} {:>8} {:>6}",
+            "", "Pass", "Fail", "Panic", "Timeout", "Skip"
+        )?;
+        for part in [Part::One, Part::Two] {
+            let [pass, fail, panic, timeout, skip] = self.outcome_counts(part);
+            writeln!(
+                f,
+                "{:<8} {:>6} {:>6} {:>6} {:>8} {:>6}",
+                format!("Part {}", part),
+                pass,
+                fail,
+                panic,
+                timeout,
+                skip
+            )?;
+        }
+
+        let (correct, wrong, unknown) = self.verdict_counts();
+        writeln!(
+            f,
+            "\nAnswers: {} correct, {} wrong, {} unknown",
+            correct, wrong, unknown
+        )?;
+        let puzzles: Duration = self.timings.iter().map(|t| t.time).sum();
+        write!(f, "Total duration: {:.0?} wall", self.elapsed)?;
+        if let Some(cpu) = self.cpu {
+            write!(f, ", {:.0?} CPU", cpu)?;
+        }
+        write!(f, ", {:.0?} summed over puzzles", puzzles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(year: u16, day: u8, millis: u64) -> Timing {
+        Timing {
+            year: Year(year),
+            day: Day(day),
+            title: "Stub",
+            time: Duration::from_millis(millis),
+            statuses: vec![
+                (Part::One, Status::Answered(Verdict::Correct)),
+                (Part::Two, Status::Answered(Verdict::Unknown)),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_year_totals() {
+        let summary = Summary {
+            timings: vec![timing(2015, 1, 10), timing(2015, 2, 5), timing(2024, 1, 7)],
+            elapsed: Duration::from_millis(30),
+            cpu: None,
+        };
+
+        assert_eq!(
+            summary.year_totals(),
+            vec![
+                (Year(2015), Duration::from_millis(15)),
+                (Year(2024), Duration::from_millis(7)),
+            ]
+        );
+        assert_eq!(summary.verdict_counts(), (3, 0, 3));
+        assert_eq!(summary.outcome_counts(Part::Two), [3, 0, 0, 0, 0]);
+        assert!(summary.passed());
+    }
+
+    #[test]
+    fn test_outcome_counts() {
+        let mut failing = timing(2024, 6, 10);
+        failing.statuses = vec![
+            (Part::One, Status::Panicked("No path found".into())),
+            (Part::Two, Status::TimedOut),
+        ];
+        let mut wrong = timing(2024, 7, 10);
+        wrong.statuses[1].1 = Status::Answered(Verdict::Wrong {
+            expected: 42.into(),
+        });
+
+        let mut skipped = timing(2015, 19, 10);
+        skipped.statuses[1].1 = Status::Skipped(Tag::Broken);
+
+        let summary = Summary {
+            timings: vec![timing(2024, 5, 10), failing, wrong],
+            elapsed: Duration::from_millis(30),
+            cpu: None,
+        };
+
+        assert_eq!(summary.outcome_counts(Part::One), [2, 0, 1, 0, 0]);
+        assert_eq!(summary.outcome_counts(Part::Two), [1, 1, 0, 1, 0]);
+        assert!(!summary.passed());
+        assert!(summary.to_string().contains("1: panicked, 2: timed out"));
+
+        let summary = Summary {
+            timings: vec![timing(2024, 5, 10), skipped],
+            ..Summary::default()
+        };
+        assert_eq!(summary.outcome_counts(Part::Two), [1, 0, 0, 0, 1]);
+        assert!(summary.passed());
+        assert!(summary
+            .to_string()
+            .contains("1: correct, 2: skipped (broken)"));
+    }
+
+    struct Stub(Part);
+
+    impl crate::Solution for Stub {
+        type Parsed<'a> = ();
+
+        fn year(&self) -> Year {
+            Year(2024)
+        }
+
+        fn day(&self) -> Day {
+            Day(1)
+        }
+
+        fn title(&self) -> &'static str {
+            "Stub"
+        }
+
+        fn parse(&self, _input: &Input) -> PuzzleResult<()> {
+            Ok(())
+        }
+
+        fn part1(&self, _parsed: &()) -> PuzzleResult<crate::Answer> {
+            match self.0 {
+                Part::One => panic!("No path found"),
+                Part::Two => {
+                    viz::record(|| Frame::new(1, 1));
+                    Ok(1.into())
+                }
+            }
+        }
+
+        fn part2(&self, _parsed: &()) -> PuzzleResult<crate::Answer> {
+            thread::sleep(Duration::from_secs(1));
+            Ok(2.into())
+        }
+    }
+
+    #[test]
+    fn test_solve_isolated() {
+        static PANICS: Stub = Stub(Part::One);
+        let attempt = solve_isolated(
+            &PANICS,
+            PathBuf::new(),
+            Some(Part::One),
+            None,
+            false,
+            &mut String::new(),
+        );
+        assert!(attempt.parsed);
+        let Some(Status::Panicked(msg)) = attempt.failure else {
+            panic!("expected a panic, got {:?}", attempt.failure);
+        };
+        assert!(msg.starts_with("No path found at "));
+        assert!(msg.contains("runner.rs"));
+
+        static SLOW: Stub = Stub(Part::Two);
+        let timeout = Some(Duration::from_millis(50));
+        let attempt = solve_isolated(
+            &SLOW,
+            PathBuf::new(),
+            None,
+            timeout,
+            true,
+            &mut String::new(),
+        );
+        assert_eq!(attempt.parts.len(), 1);
+        assert_eq!(attempt.parts[0].1.len(), 1);
+        assert_eq!(attempt.failure, Some(Status::TimedOut));
+    }
+}
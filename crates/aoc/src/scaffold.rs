@@ -0,0 +1,243 @@
+use crate::cache::is_workspace_root;
+use crate::{AocCache, PuzzleError, PuzzleResult};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The day every new day starts from.
+const TEMPLATE: &str = include_str!("../../aoc24/src/e00.rs");
+
+/// The workspace with one `crates/aocYY` crate per year.
+#[derive(Clone, Debug)]
+pub struct Workspace {
+    root: PathBuf,
+}
+
+impl Workspace {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The workspace that `start` is in.
+    pub fn find(start: &Path) -> PuzzleResult<Self> {
+        start
+            .ancestors()
+            .find(|dir| is_workspace_root(dir))
+            .map(Self::new)
+            .ok_or_else(|| PuzzleError::Input(format!("{} is not in a workspace", start.display())))
+    }
+
+    /// The `src` directory of the crate for `year`.
+    fn src(&self, year: u16) -> PathBuf {
+        self.root
+            .join("crates")
+            .join(format!("aoc{:02}", year % 100))
+            .join("src")
+    }
+}
+
+/// Adds a day to the crate for its year: the module from the template, its
+/// `mod` in `lib.rs` and the input in the cache.
+///
+/// Existing files are kept, so it is safe to run again, e.g. after the
+/// input failed to download.
+pub fn new_day(cache: &AocCache, workspace: &Workspace, year: u16, day: u8) -> PuzzleResult<()> {
+    let src = workspace.src(year);
+    let lib = src.join("lib.rs");
+    if !lib.is_file() {
+        return Err(PuzzleError::Input(format!(
+            "There is no crate for {year}, expected {}",
+            lib.display()
+        )));
+    }
+
+    let title = cache.title(year, day)?;
+    let module = format!("e{day:02}");
+
+    let path = src.join(format!("{module}.rs"));
+    if path.exists() {
+        println!("Keeping {}", path.display());
+    } else {
+        let sample = cache
+            .get_sample_path(year, day, 1)
+            .and_then(|path| read(&path))
+            .ok();
+        write(&path, &generate(year, day, &title, sample.as_deref()))?;
+        println!("Created {} for {title}", path.display());
+    }
+
+    let modules = read(&lib)?;
+    let declared = declare(&modules, &module);
+    if declared == modules {
+        println!("{module} is already declared");
+    } else {
+        write(&lib, &declared)?;
+        println!("Declared {module} in {}", lib.display());
+    }
+
+    let input = cache.get_path(year, day)?;
+    println!("Input in {}", input.display());
+
+    Ok(())
+}
+
+/// The template turned into the module for `day`, with the sample in its
+/// tests when there is one.
+fn generate(year: u16, day: u8, title: &str, sample: Option<&str>) -> String {
+    let code = TEMPLATE
+        .replace(
+            "year = 2024, day = 0,",
+            &format!("year = {year}, day = {day},"),
+        )
+        .replace("(2024, 0, ", &format!("({year}, {day}, "))
+        .replace("\"Foo\"", &format!("{title:?}"))
+        .replace("Foo", &type_name(title));
+
+    let Some(sample) = sample else {
+        return code;
+    };
+
+    let tests = code.find("#[cfg(test)]").unwrap_or(code.len());
+    let escaped = sample.replace('\\', "\\\\").replace('"', "\\\"");
+    let newline = if sample.ends_with('\n') { "" } else { "\n" };
+    format!(
+        r#"{}#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    const SAMPLE: &str = "\
+{escaped}{newline}";
+
+    #[test]
+    fn test_part1() {{
+        assert_eq!(part1(&SAMPLE.into()).unwrap(), 0);
+    }}
+
+    #[test]
+    fn test_part2() {{
+        assert_eq!(part2(&SAMPLE.into()).unwrap(), 0);
+    }}
+}}
+"#,
+        &code[..tests]
+    )
+}
+
+/// `lib.rs` with `mod <module>;` added in day order, unless it is there
+/// already. The day registers itself with `#[aoc::solution]`.
+fn declare(lib: &str, module: &str) -> String {
+    let mut lines: Vec<&str> = lib.lines().collect();
+    let declared = |line: &str| {
+        line.strip_prefix("pub ")
+            .unwrap_or(line)
+            .strip_prefix("mod e")
+            .and_then(|rest| rest.strip_suffix(';'))
+            .map(|day| format!("e{day}"))
+    };
+
+    if lines
+        .iter()
+        .any(|&line| declared(line).as_deref() == Some(module))
+    {
+        return lib.to_string();
+    }
+
+    // Match the visibility of the other days
+    let public = lines.iter().any(|line| line.starts_with("pub mod e"));
+    let declaration = format!("{}mod {module};", if public { "pub " } else { "" });
+
+    let modules: Vec<usize> = (0..lines.len())
+        .filter(|&i| declared(lines[i]).is_some())
+        .collect();
+    let at = modules
+        .iter()
+        .find(|&&i| declared(lines[i]).is_some_and(|day| day.as_str() > module))
+        .copied()
+        .or_else(|| modules.last().map(|&i| i + 1))
+        .unwrap_or(lines.len());
+    lines.insert(at, &declaration);
+
+    lines.join("\n") + "\n"
+}
+
+/// The title as a type name, e.g. `GuardGallivant` for "Guard Gallivant".
+fn type_name(title: &str) -> String {
+    let name: String = title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect();
+
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("Day{name}")
+    }
+}
+
+fn read(path: &Path) -> PuzzleResult<String> {
+    fs::read_to_string(path).map_err(|error| PuzzleError::IO {
+        msg: format!("Can't read {}", path.display()),
+        error,
+    })
+}
+
+fn write(path: &Path, contents: &str) -> PuzzleResult<()> {
+    fs::write(path, contents).map_err(|error| PuzzleError::IO {
+        msg: format!("Can't write {}", path.display()),
+        error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "\
+//! The 2024 puzzles.
+
+#[cfg(test)]
+mod e00;
+mod e01;
+mod e03;
+";
+
+    #[test]
+    fn test_type_name() {
+        assert_eq!(type_name("Guard Gallivant"), "GuardGallivant");
+        assert_eq!(type_name("Some Assembly Required"), "SomeAssemblyRequired");
+        assert_eq!(type_name("Chronal Co-ordinates"), "ChronalCoOrdinates");
+        assert_eq!(type_name("2 Keypads"), "Day2Keypads");
+    }
+
+    #[test]
+    fn test_generate() {
+        let code = generate(2015, 6, "Guard Gallivant", Some("..#\n\\\"\n"));
+        assert!(
+            code.contains("#[aoc::solution(year = 2015, day = 6, title = \"Guard Gallivant\")]")
+        );
+        assert!(code.contains("pub struct GuardGallivant;"));
+        assert!(code.contains("const SAMPLE: &str = \"\\\n..#\n\\\\\\\"\n\";"));
+        assert!(!code.contains("Input::sample"));
+
+        let code = generate(2015, 6, "Guard Gallivant", None);
+        assert!(code.contains("Input::sample(2015, 6, 1)"));
+    }
+
+    #[test]
+    fn test_declare() {
+        let lib = declare(LIB, "e02");
+        assert!(lib.contains("mod e01;\nmod e02;\nmod e03;\n"));
+        assert_eq!(declare(&lib, "e02"), lib);
+
+        assert!(declare(LIB, "e04").ends_with("mod e03;\nmod e04;\n"));
+
+        let lib = LIB.replace("mod e0", "pub mod e0");
+        assert!(declare(&lib, "e02").contains("pub mod e01;\npub mod e02;\n"));
+    }
+}
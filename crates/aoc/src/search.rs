@@ -0,0 +1,331 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The outcome of a search: the cost of every settled state, every
+/// predecessor on a shortest path to it and the goal states at the lowest
+/// cost.
+#[derive(Clone, Debug)]
+pub struct Searched<S, C> {
+    states: Vec<S>,
+    index: HashMap<S, usize>,
+    costs: Vec<C>,
+    settled: Vec<bool>,
+    predecessors: Vec<Vec<usize>>,
+    goals: Vec<usize>,
+}
+
+impl<S, C> Searched<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord,
+{
+    fn new() -> Self {
+        Self {
+            states: Vec::new(),
+            index: HashMap::new(),
+            costs: Vec::new(),
+            settled: Vec::new(),
+            predecessors: Vec::new(),
+            goals: Vec::new(),
+        }
+    }
+
+    /// Records that `state` can be reached at `cost` from `from`, returning
+    /// the index of the state if this is a new lowest cost.
+    fn relax(&mut self, state: S, cost: C, from: Option<usize>) -> Option<usize> {
+        match self.index.entry(state) {
+            Entry::Vacant(entry) => {
+                let i = self.states.len();
+                self.states.push(entry.key().clone());
+                entry.insert(i);
+                self.costs.push(cost);
+                self.settled.push(false);
+                self.predecessors.push(from.into_iter().collect());
+                Some(i)
+            }
+            Entry::Occupied(entry) => {
+                let i = *entry.get();
+                if self.settled[i] || cost > self.costs[i] {
+                    None
+                } else if cost == self.costs[i] {
+                    self.predecessors[i].extend(from);
+                    None
+                } else {
+                    self.costs[i] = cost;
+                    self.predecessors[i] = from.into_iter().collect();
+                    Some(i)
+                }
+            }
+        }
+    }
+
+    /// The first goal state that was reached.
+    pub fn goal(&self) -> Option<&S> {
+        self.goals.first().map(|&i| &self.states[i])
+    }
+
+    /// Every goal state that can be reached at the lowest cost.
+    pub fn goals(&self) -> impl Iterator<Item = &S> {
+        self.goals.iter().map(|&i| &self.states[i])
+    }
+
+    /// The cost of reaching the goal.
+    pub fn cost(&self) -> Option<C> {
+        self.goals.first().map(|&i| self.costs[i])
+    }
+
+    /// The lowest cost of reaching `state`, if the search got that far.
+    pub fn cost_to(&self, state: &S) -> Option<C> {
+        let i = *self.index.get(state)?;
+        self.settled[i].then(|| self.costs[i])
+    }
+
+    /// Every settled state with its lowest cost.
+    pub fn reached(&self) -> impl Iterator<Item = (&S, C)> {
+        self.states
+            .iter()
+            .zip(&self.costs)
+            .zip(&self.settled)
+            .filter(|(_, &settled)| settled)
+            .map(|((state, &cost), _)| (state, cost))
+    }
+
+    /// One shortest path from the start to the goal, both included.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goal()?)
+    }
+
+    /// One shortest path from the start to `state`, both included.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        self.cost_to(state)?;
+        let mut i = self.index[state];
+        let mut path = vec![self.states[i].clone()];
+        while let Some(&previous) = self.predecessors[i].first() {
+            path.push(self.states[previous].clone());
+            i = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// The states just before `state` on its shortest paths.
+    pub fn predecessors(&self, state: &S) -> impl Iterator<Item = &S> {
+        self.index
+            .get(state)
+            .into_iter()
+            .flat_map(|&i| &self.predecessors[i])
+            .map(|&i| &self.states[i])
+    }
+
+    /// Every state on any shortest path from the start to `state`.
+    pub fn on_shortest_paths_to(&self, state: &S) -> HashSet<&S> {
+        let mut seen = HashSet::new();
+        let mut queue: Vec<_> = self.index.get(state).copied().into_iter().collect();
+        while let Some(i) = queue.pop() {
+            if seen.insert(&self.states[i]) {
+                queue.extend(&self.predecessors[i]);
+            }
+        }
+        seen
+    }
+
+    /// Every state on any shortest path from the start to any of the goals.
+    pub fn on_shortest_paths(&self) -> HashSet<&S> {
+        self.goals()
+            .flat_map(|goal| self.on_shortest_paths_to(goal))
+            .collect()
+    }
+}
+
+/// Breadth-first search where every step costs one.
+///
+/// The search stops once every state as close as the first one that `goal`
+/// accepts has been checked. Pass `|_| false` to search everything reachable
+/// from the start.
+pub fn bfs<S, N, I>(
+    start: S,
+    mut neighbours: N,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Searched<S, usize>
+where
+    S: Clone + Eq + Hash,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    let mut searched = Searched::new();
+    let mut queue = VecDeque::from_iter(searched.relax(start, 0, None));
+
+    while let Some(i) = queue.pop_front() {
+        if searched.cost().is_some_and(|cost| searched.costs[i] > cost) {
+            break;
+        }
+
+        searched.settled[i] = true;
+        if goal(&searched.states[i]) {
+            searched.goals.push(i);
+        }
+        // Every step costs one, so nothing past a goal is as close as it.
+        if !searched.goals.is_empty() {
+            continue;
+        }
+
+        let cost = searched.costs[i] + 1;
+        for next in neighbours(&searched.states[i]) {
+            queue.extend(searched.relax(next, cost, Some(i)));
+        }
+    }
+
+    searched
+}
+
+/// Dijkstra's algorithm, for neighbours listed with the cost of the step
+/// there. Costs must not be negative.
+pub fn dijkstra<S, C, N, I>(start: S, neighbours: N, goal: impl FnMut(&S) -> bool) -> Searched<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, neighbours, |_| C::default(), goal)
+}
+
+/// A* search, guided by a `heuristic` that never overestimates the cost
+/// left to the goal and never drops by more than the cost of a step.
+///
+/// The search goes on after the first goal until nothing left can reach
+/// another goal at the same cost.
+pub fn astar<S, C, N, I, H>(
+    start: S,
+    mut neighbours: N,
+    mut heuristic: H,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Searched<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    H: FnMut(&S) -> C,
+{
+    let mut searched = Searched::new();
+    let mut queue = BinaryHeap::new();
+    let start_cost = C::default();
+    let estimate = heuristic(&start);
+    if let Some(i) = searched.relax(start, start_cost, None) {
+        queue.push(Reverse((start_cost + estimate, start_cost, i)));
+    }
+
+    while let Some(Reverse((estimate, cost, i))) = queue.pop() {
+        if searched
+            .cost()
+            .is_some_and(|goal_cost| estimate > goal_cost)
+        {
+            break;
+        }
+        if searched.settled[i] || cost > searched.costs[i] {
+            continue;
+        }
+
+        searched.settled[i] = true;
+        if goal(&searched.states[i]) {
+            searched.goals.push(i);
+            continue;
+        }
+
+        for (next, step) in neighbours(&searched.states[i]) {
+            let estimate = heuristic(&next);
+            let next_cost = cost + step;
+            if let Some(j) = searched.relax(next, next_cost, Some(i)) {
+                queue.push(Reverse((next_cost + estimate, next_cost, j)));
+            }
+        }
+    }
+
+    searched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two ways from 1 to 10: doubling or adding one.
+    fn steps(n: &u32) -> Vec<(u32, u32)> {
+        vec![(n * 2, 1), (n + 1, 1)]
+            .into_iter()
+            .filter(|&(n, _)| n <= 10)
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let searched = bfs(1u32, |n| steps(n).into_iter().map(|(n, _)| n), |&n| n == 10);
+        assert_eq!(searched.cost(), Some(4));
+        assert_eq!(searched.path().unwrap().len(), 5);
+        assert_eq!(searched.cost_to(&4), Some(2));
+
+        let everything = bfs(1u32, |n| steps(n).into_iter().map(|(n, _)| n), |_| false);
+        assert_eq!(everything.goal(), None);
+        assert_eq!(everything.reached().count(), 10);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // A diamond with two equally short paths and a long one.
+        let edges = |s: &char| match s {
+            'a' => vec![('b', 1), ('c', 1), ('d', 5)],
+            'b' | 'c' | 'd' => vec![('e', 1)],
+            _ => vec![],
+        };
+
+        let searched = dijkstra('a', edges, |&s| s == 'e');
+        assert_eq!(searched.cost(), Some(2));
+        assert_eq!(searched.path().unwrap().len(), 3);
+        let mut predecessors: Vec<_> = searched.predecessors(&'e').collect();
+        predecessors.sort();
+        assert_eq!(predecessors, [&'b', &'c']);
+        assert_eq!(searched.on_shortest_paths().len(), 4);
+    }
+
+    #[test]
+    fn test_equal_goals() {
+        // Two goals at the same cost, and one further away.
+        let edges = |s: &char| match s {
+            'a' => vec![('b', 1), ('c', 1), ('d', 1)],
+            'b' => vec![('x', 1)],
+            'c' => vec![('y', 1)],
+            'd' => vec![('z', 2)],
+            _ => vec![],
+        };
+        let is_goal = |s: &char| ['x', 'y', 'z'].contains(s);
+
+        let searched = dijkstra('a', edges, is_goal);
+        assert_eq!(searched.cost(), Some(2));
+        let mut goals: Vec<_> = searched.goals().collect();
+        goals.sort();
+        assert_eq!(goals, [&'x', &'y']);
+        let mut on_paths: Vec<_> = searched.on_shortest_paths().into_iter().collect();
+        on_paths.sort();
+        assert_eq!(on_paths, [&'a', &'b', &'c', &'x', &'y']);
+
+        let searched = bfs('a', |s| edges(s).into_iter().map(|(s, _)| s), is_goal);
+        assert_eq!(searched.goals().count(), 3);
+        assert_eq!(searched.on_shortest_paths().len(), 7);
+    }
+
+    #[test]
+    fn test_astar() {
+        let goal = (3i32, 4i32);
+        let searched = astar(
+            (0i32, 0i32),
+            |&(x, y)| [((x + 1, y), 1), ((x, y + 1), 1), ((x - 1, y), 1)],
+            |&(x, y)| (goal.0 - x).abs() + (goal.1 - y).abs(),
+            |&p| p == goal,
+        );
+        assert_eq!(searched.cost(), Some(7));
+        assert_eq!(searched.path().unwrap().last(), Some(&goal));
+    }
+}
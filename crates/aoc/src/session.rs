@@ -0,0 +1,125 @@
+use crate::{PuzzleError, PuzzleResult};
+use std::env;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// A place the session token can be read from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SessionSource {
+    Env(&'static str),
+    File(PathBuf),
+}
+
+impl SessionSource {
+    fn read(&self) -> Option<String> {
+        let token = match self {
+            SessionSource::Env(name) => env::var(name).ok()?,
+            SessionSource::File(path) => fs::read_to_string(path).ok()?,
+        };
+
+        let token = token.trim();
+        (!token.is_empty()).then(|| token.to_string())
+    }
+}
+
+impl Display for SessionSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionSource::Env(name) => write!(f, "${}", name),
+            SessionSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Where the session token is looked for, in order: the `AOC_SESSION`
+/// environment variable, `session.txt` in the cache and `aoc/session.txt`
+/// in the XDG config directory.
+pub fn session_sources(cache_root: &Path) -> Vec<SessionSource> {
+    let mut sources = vec![
+        SessionSource::Env(SESSION_ENV),
+        SessionSource::File(cache_root.join("session.txt")),
+    ];
+
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+    if let Some(config_home) = config_home {
+        sources.push(SessionSource::File(
+            config_home.join("aoc").join("session.txt"),
+        ));
+    }
+
+    sources
+}
+
+/// The token from the first source that has one.
+pub fn find_session(sources: &[SessionSource]) -> PuzzleResult<String> {
+    sources
+        .iter()
+        .find_map(SessionSource::read)
+        .ok_or_else(|| PuzzleError::MissingSession {
+            searched: sources.iter().map(|source| source.to_string()).collect(),
+        })
+}
+
+/// Checks the token with a request for the settings page, which redirects
+/// to the login page unless the session is valid.
+pub fn validate_session(base_url: &str, session: &str) -> PuzzleResult<()> {
+    let url = format!("{base_url}/settings");
+    let agent = ureq::AgentBuilder::new().redirects(0).build();
+
+    let response = agent
+        .get(&url)
+        .set("Cookie", &format!("session={}", session))
+        .call();
+
+    match response {
+        Ok(response) if response.status() == 200 => Ok(()),
+        Ok(response) => Err(PuzzleError::InvalidSession(format!(
+            "{url} replied {} {}, the session token is likely expired",
+            response.status(),
+            response.status_text()
+        ))),
+        Err(ureq::Error::Status(code, _)) => Err(PuzzleError::InvalidSession(format!(
+            "{url} replied {code}, the session token is likely expired"
+        ))),
+        Err(e) => Err(PuzzleError::DownloadFailed(
+            format!("Failed to validate the session with {url}: {e}"),
+            e.into(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        fs::write(dir.join("empty.txt"), " \n").unwrap();
+        fs::write(dir.join("session.txt"), "abc123\n").unwrap();
+
+        let sources = [
+            SessionSource::Env("AOC_SESSION_TEST_UNSET"),
+            SessionSource::File(dir.join("missing.txt")),
+            SessionSource::File(dir.join("empty.txt")),
+            SessionSource::File(dir.join("session.txt")),
+        ];
+        assert_eq!(find_session(&sources).unwrap(), "abc123");
+
+        match find_session(&sources[..3]) {
+            Err(PuzzleError::MissingSession { searched }) => {
+                assert_eq!(searched[0], "$AOC_SESSION_TEST_UNSET");
+                assert_eq!(searched.len(), 3);
+            }
+            result => panic!("Unexpected {result:?}"),
+        }
+    }
+}
@@ -0,0 +1,333 @@
+//! An interactive debugger for puzzles that are solved by simulating
+//! something one step at a time.
+//!
+//! A day implements [`Simulation`] for its state and steps through it from
+//! [`Solution::debug`](crate::Solution::debug):
+//!
+//! ```ignore
+//! fn debug(&self, input: &&str, _part: Part) -> PuzzleResult<()> {
+//!     sim::debug(Scene::parse(input)?)
+//! }
+//! ```
+//!
+//! Then `aoc debug 2024 15` draws the scene and waits for commands to step
+//! forward and back, jump to a step, run until the state matches and
+//! inspect it.
+
+use crate::viz::Frame;
+use crate::{AocCache, Input, Part, PuzzleError, PuzzleResult, Registry};
+use std::io;
+use std::io::{BufRead, Write};
+
+/// How often the state is saved, in steps, to go back to. Going back
+/// replays the steps from the last snapshot before it.
+pub const SNAPSHOT_INTERVAL: usize = 100;
+
+/// The most steps that `until` takes looking for a match.
+const UNTIL_LIMIT: usize = 100_000;
+
+const HELP: &str = "\
+Commands:
+  n [count]    step forward, also an empty line
+  b [count]    step back
+  g <step>     go to a step
+  u <text>     run until the inspected state contains the text
+  i            inspect the state
+  h            show this help
+  q            quit";
+
+/// A state that changes in steps.
+pub trait Simulation: Clone {
+    /// Takes one step, or returns false when the simulation has ended.
+    fn step(&mut self) -> bool;
+
+    /// Draws the state.
+    fn frame(&self) -> Frame;
+
+    /// What the frame doesn't show, e.g. counts or the next move.
+    fn inspect(&self) -> String {
+        String::new()
+    }
+}
+
+/// Steps a simulation forward and back.
+pub struct Debugger<S> {
+    current: S,
+    step: usize,
+    /// The state at every `interval` steps, from the start.
+    snapshots: Vec<S>,
+    interval: usize,
+    /// The last step, once it has been reached.
+    end: Option<usize>,
+}
+
+impl<S: Simulation> Debugger<S> {
+    pub fn new(simulation: S) -> Self {
+        Self {
+            snapshots: vec![simulation.clone()],
+            current: simulation,
+            step: 0,
+            interval: SNAPSHOT_INTERVAL,
+            end: None,
+        }
+    }
+
+    /// Saves the state every `interval` steps instead.
+    pub fn with_interval(mut self, interval: usize) -> Self {
+        self.interval = interval.max(1);
+        self
+    }
+
+    pub fn state(&self) -> &S {
+        &self.current
+    }
+
+    /// The number of steps taken from the start.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Takes up to `steps` steps. Returns false if the simulation ended first.
+    pub fn forward(&mut self, steps: usize) -> bool {
+        for _ in 0..steps {
+            if self.end == Some(self.step) || !self.current.step() {
+                self.end = Some(self.step);
+                return false;
+            }
+            self.step += 1;
+            if self.step == self.snapshots.len() * self.interval {
+                self.snapshots.push(self.current.clone());
+            }
+        }
+        true
+    }
+
+    /// Goes back `steps` steps, or to the start.
+    pub fn back(&mut self, steps: usize) {
+        self.goto(self.step.saturating_sub(steps));
+    }
+
+    /// Goes to a step. Returns false if the simulation ended before it.
+    pub fn goto(&mut self, step: usize) -> bool {
+        if step < self.step {
+            let i = step / self.interval;
+            self.current = self.snapshots[i].clone();
+            self.step = i * self.interval;
+        }
+        self.forward(step - self.step)
+    }
+
+    /// Steps until `predicate` holds, for at most `limit` steps. Returns
+    /// false if it never did.
+    pub fn run_until(&mut self, limit: usize, mut predicate: impl FnMut(&S) -> bool) -> bool {
+        for _ in 0..limit {
+            if !self.forward(1) {
+                return false;
+            }
+            if predicate(&self.current) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Draws the state and runs commands from `input` until it ends or a
+    /// `q`.
+    pub fn run(&mut self, input: &mut impl BufRead, out: &mut impl Write) -> io::Result<()> {
+        let mut message = Some("Type h for help".to_string());
+        loop {
+            write!(out, "\x1B[H\x1B[2J{}", self.current.frame().ansi())?;
+            let ended = if self.end == Some(self.step) {
+                " (ended)"
+            } else {
+                ""
+            };
+            writeln!(out, "Step {}{}", self.step, ended)?;
+            if let Some(message) = message.take() {
+                writeln!(out, "{}", message)?;
+            }
+            write!(out, "> ")?;
+            out.flush()?;
+
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                return Ok(());
+            }
+            message = match Command::parse(&line) {
+                Ok(Command::Quit) => return Ok(()),
+                Ok(command) => self.execute(command),
+                Err(error) => Some(error),
+            };
+        }
+    }
+
+    /// Carries out a command and returns what to tell about it.
+    fn execute(&mut self, command: Command) -> Option<String> {
+        let ended = |debugger: &Self| format!("The simulation ended at step {}", debugger.step);
+        match command {
+            Command::Forward(steps) => (!self.forward(steps)).then(|| ended(self)),
+            Command::Back(steps) => {
+                self.back(steps);
+                None
+            }
+            Command::Goto(step) => (!self.goto(step)).then(|| ended(self)),
+            Command::Until(text) => {
+                if self.run_until(UNTIL_LIMIT, |state| state.inspect().contains(&text)) {
+                    Some(format!("Found '{}'", text))
+                } else if self.end == Some(self.step) {
+                    Some(format!("{}, without finding '{}'", ended(self), text))
+                } else {
+                    Some(format!("Didn't find '{}' in {} steps", text, UNTIL_LIMIT))
+                }
+            }
+            Command::Inspect => match self.current.inspect() {
+                state if state.is_empty() => Some("Nothing to inspect".to_string()),
+                state => Some(state),
+            },
+            Command::Help => Some(HELP.to_string()),
+            Command::Quit => None,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum Command {
+    Forward(usize),
+    Back(usize),
+    Goto(usize),
+    Until(String),
+    Inspect,
+    Help,
+    Quit,
+}
+
+impl Command {
+    fn parse(line: &str) -> Result<Self, String> {
+        let line = line.trim();
+        let (name, arg) = line.split_once(' ').unwrap_or((line, ""));
+        let arg = arg.trim();
+        let number = |default: Option<usize>| match arg {
+            "" => default.ok_or_else(|| format!("'{}' needs a step", name)),
+            arg => arg
+                .parse::<usize>()
+                .map_err(|_| format!("Invalid number '{}'", arg)),
+        };
+
+        match name {
+            "" | "n" | "next" => number(Some(1)).map(Command::Forward),
+            "b" | "back" => number(Some(1)).map(Command::Back),
+            "g" | "goto" => number(None).map(Command::Goto),
+            "u" | "until" if arg.is_empty() => Err(format!("'{}' needs a text", name)),
+            "u" | "until" => Ok(Command::Until(arg.to_string())),
+            "i" | "inspect" => Ok(Command::Inspect),
+            "h" | "help" | "?" => Ok(Command::Help),
+            "q" | "quit" => Ok(Command::Quit),
+            _ => Err(format!("Unknown command '{}', type h for help", name)),
+        }
+    }
+}
+
+/// Steps through `simulation` in the terminal.
+pub fn debug<S: Simulation>(simulation: S) -> PuzzleResult<()> {
+    Debugger::new(simulation).run(&mut io::stdin().lock(), &mut io::stdout())?;
+    Ok(())
+}
+
+/// Steps through a part of a registered puzzle, with its input or with
+/// one of the samples from its page.
+pub fn debug_puzzle(
+    registry: &Registry,
+    cache: &AocCache,
+    year: u16,
+    day: u8,
+    part: Part,
+    sample: Option<usize>,
+) -> PuzzleResult<()> {
+    let puzzle = registry
+        .get(year, day)
+        .ok_or_else(|| PuzzleError::Input(format!("No puzzle for {year} day {day}")))?;
+    let path = match sample {
+        Some(n) => cache.get_sample_path(year, day, n)?,
+        None => cache.get_path(year, day)?,
+    };
+    puzzle.debug(&Input::from_path(path), part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Counts to a limit.
+    #[derive(Clone)]
+    struct Counter(usize, usize);
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            if self.0 == self.1 {
+                return false;
+            }
+            self.0 += 1;
+            true
+        }
+
+        fn frame(&self) -> Frame {
+            Frame::new(1, 1).with_caption(self.0.to_string())
+        }
+
+        fn inspect(&self) -> String {
+            format!("count {}", self.0)
+        }
+    }
+
+    #[test]
+    fn test_debugger() {
+        let mut debugger = Debugger::new(Counter(0, 10)).with_interval(3);
+        assert!(debugger.forward(7));
+        assert_eq!(debugger.snapshots.len(), 3);
+
+        debugger.back(2);
+        assert_eq!((debugger.step(), debugger.state().0), (5, 5));
+        assert!(debugger.goto(1));
+        assert_eq!(debugger.state().0, 1);
+
+        assert!(debugger.run_until(100, |counter| counter.0 == 9));
+        assert_eq!(debugger.step(), 9);
+        assert!(!debugger.forward(5));
+        assert_eq!(debugger.step(), 10);
+        assert!(!debugger.forward(1));
+        assert_eq!(debugger.step(), 10);
+
+        assert!(debugger.goto(4));
+        assert_eq!(debugger.state().0, 4);
+    }
+
+    #[test]
+    fn test_command() {
+        assert_eq!(Command::parse("\n"), Ok(Command::Forward(1)));
+        assert_eq!(Command::parse("n 20\n"), Ok(Command::Forward(20)));
+        assert_eq!(Command::parse("g 5"), Ok(Command::Goto(5)));
+        assert_eq!(
+            Command::parse("u count 1"),
+            Ok(Command::Until("count 1".into()))
+        );
+        assert!(Command::parse("g").is_err());
+        assert!(Command::parse("b x").is_err());
+        assert!(Command::parse("x").is_err());
+    }
+
+    #[test]
+    fn test_run() {
+        let mut debugger = Debugger::new(Counter(0, 10));
+        let mut input = Cursor::new("n 3\n\nu count 8\ni\nb 6\nx\ng 20\nq\nn\n");
+        let mut out = Vec::new();
+        debugger.run(&mut input, &mut out).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Found 'count 8'"));
+        assert!(out.contains("Step 2\n"));
+        assert!(out.contains("Unknown command 'x'"));
+        assert!(out.contains("The simulation ended at step 10"));
+        assert_eq!(debugger.step(), 10);
+    }
+}
@@ -0,0 +1,389 @@
+use crate::{Day, Input, PuzzleError, PuzzleResult, Year};
+use clap::ValueEnum;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+
+/// A puzzle solution, split into parsing the input and solving the two parts.
+pub trait Solution: Sync {
+    /// The parsed input, which may borrow the text of the input, e.g. `&'a str`.
+    type Parsed<'a>;
+
+    fn year(&self) -> Year;
+    fn day(&self) -> Day;
+    fn title(&self) -> &'static str;
+
+    /// What is special about a part, e.g. that it is too slow to run every time.
+    fn tags(&self, _part: Part) -> &'static [Tag] {
+        &[]
+    }
+
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<Self::Parsed<'a>>;
+    fn part1(&self, parsed: &Self::Parsed<'_>) -> PuzzleResult<Answer>;
+    fn part2(&self, parsed: &Self::Parsed<'_>) -> PuzzleResult<Answer>;
+
+    /// Steps through a part in the [debugger](crate::sim), for days that
+    /// simulate something.
+    fn debug(&self, _parsed: &Self::Parsed<'_>, _part: Part) -> PuzzleResult<()> {
+        Err(PuzzleError::Input(format!(
+            "{} day {} has nothing to step through",
+            self.year(),
+            self.day().0
+        )))
+    }
+}
+
+/// The object safe view of a [`Solution`], as kept in the [`Registry`](crate::Registry).
+pub trait Puzzle: Sync {
+    fn year(&self) -> Year;
+    fn day(&self) -> Day;
+    fn title(&self) -> &'static str;
+
+    /// What is special about a part, e.g. that it is too slow to run every time.
+    fn tags(&self, _part: Part) -> &'static [Tag] {
+        &[]
+    }
+
+    /// Parses the input and solves the selected part, or both parts when `part` is `None`.
+    fn solve(&self, input: &Input, part: Option<Part>) -> PuzzleResult<Solved> {
+        self.solve_each(input, part, &mut |_| {})
+    }
+
+    /// Like [`Puzzle::solve`], calling `progress` after parsing and after
+    /// each part, so that a caller knows how far it got if it fails.
+    fn solve_each(
+        &self,
+        input: &Input,
+        part: Option<Part>,
+        progress: &mut dyn FnMut(Progress),
+    ) -> PuzzleResult<Solved>;
+
+    /// Parses the input and steps through a part in the [debugger](crate::sim).
+    fn debug(&self, input: &Input, part: Part) -> PuzzleResult<()>;
+}
+
+/// A step of [`Puzzle::solve_each`] that has finished.
+#[derive(Debug)]
+pub enum Progress<'a> {
+    Parsed(Duration),
+    Solved(&'a PartResult),
+}
+
+impl<S: Solution> Puzzle for S {
+    fn year(&self) -> Year {
+        Solution::year(self)
+    }
+
+    fn day(&self) -> Day {
+        Solution::day(self)
+    }
+
+    fn title(&self) -> &'static str {
+        Solution::title(self)
+    }
+
+    fn tags(&self, part: Part) -> &'static [Tag] {
+        Solution::tags(self, part)
+    }
+
+    fn solve_each(
+        &self,
+        input: &Input,
+        part: Option<Part>,
+        progress: &mut dyn FnMut(Progress),
+    ) -> PuzzleResult<Solved> {
+        solve(self, input, part, progress)
+            .map_err(|error| error.in_puzzle(Solution::year(self), Solution::day(self)))
+    }
+
+    fn debug(&self, input: &Input, part: Part) -> PuzzleResult<()> {
+        let parsed = self
+            .parse(input)
+            .map_err(|error| error.in_puzzle(Solution::year(self), Solution::day(self)))?;
+        Solution::debug(self, &parsed, part)
+    }
+}
+
+fn solve<S: Solution>(
+    solution: &S,
+    input: &Input,
+    part: Option<Part>,
+    progress: &mut dyn FnMut(Progress),
+) -> PuzzleResult<Solved> {
+    let start = Instant::now();
+    let parsed = solution.parse(input)?;
+    let parse_time = start.elapsed();
+    progress(Progress::Parsed(parse_time));
+
+    let mut parts = Vec::new();
+    for p in [Part::One, Part::Two] {
+        if part.is_some_and(|selected| selected != p) {
+            continue;
+        }
+
+        let start = Instant::now();
+        let answer = match p {
+            Part::One => solution.part1(&parsed)?,
+            Part::Two => solution.part2(&parsed)?,
+        };
+        let result = PartResult {
+            part: p,
+            answer,
+            time: start.elapsed(),
+        };
+        progress(Progress::Solved(&result));
+        parts.push(result);
+    }
+
+    Ok(Solved { parse_time, parts })
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, ValueEnum)]
+pub enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// Marks a part that shouldn't always be run.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Tag {
+    /// Takes long enough to be left out of quick runs.
+    Slow,
+    /// Gives a wrong answer or doesn't finish.
+    Broken,
+    /// Work in progress.
+    Experimental,
+}
+
+impl Display for Tag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Tag::Slow => write!(f, "slow"),
+            Tag::Broken => write!(f, "broken"),
+            Tag::Experimental => write!(f, "experimental"),
+        }
+    }
+}
+
+/// The tags of the parts to leave out of a run.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Skip(Vec<Tag>);
+
+/// Broken and experimental parts are only run when asked for.
+impl Default for Skip {
+    fn default() -> Self {
+        Self(vec![Tag::Broken, Tag::Experimental])
+    }
+}
+
+impl Skip {
+    /// The default, with the `skip` tags added and the `include` ones removed.
+    pub fn new(skip: &[Tag], include: &[Tag]) -> Self {
+        let mut tags = Self::default().0;
+        tags.extend(skip);
+        tags.retain(|tag| !include.contains(tag));
+        Self(tags)
+    }
+
+    /// Why `part` of `puzzle` is left out, if it is.
+    pub fn reason(&self, puzzle: &dyn Puzzle, part: Part) -> Option<Tag> {
+        puzzle
+            .tags(part)
+            .iter()
+            .copied()
+            .find(|tag| self.0.contains(tag))
+    }
+
+    /// The `selected` parts of `puzzle` that aren't left out, as the part to
+    /// pass to [`Puzzle::solve`], or `None` if there is nothing to solve.
+    pub fn select(&self, puzzle: &dyn Puzzle, selected: Option<Part>) -> Option<Option<Part>> {
+        let parts: Vec<Part> = [Part::One, Part::Two]
+            .into_iter()
+            .filter(|&p| selected.is_none_or(|s| s == p))
+            .filter(|&p| self.reason(puzzle, p).is_none())
+            .collect();
+
+        match parts[..] {
+            [] => None,
+            [part] => Some(Some(part)),
+            _ => Some(None),
+        }
+    }
+}
+
+/// The answer to one part, in the form it is entered on the puzzle page.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Answer(String);
+
+impl Answer {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer(value.to_string())
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer(value.to_string())
+                }
+            }
+        )*
+    };
+}
+
+answer_from_integer!(i16, i32, i64, u16, u32, u64, usize);
+
+#[derive(Clone, Debug)]
+pub struct PartResult {
+    pub part: Part,
+    pub answer: Answer,
+    pub time: Duration,
+}
+
+/// The outcome of [`Puzzle::solve`] with the time spent in each phase.
+#[derive(Debug)]
+pub struct Solved {
+    pub parse_time: Duration,
+    pub parts: Vec<PartResult>,
+}
+
+impl Solved {
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.parts.iter().map(|p| p.time).sum::<Duration>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Parsed<'a> = Vec<i32>;
+
+        fn year(&self) -> Year {
+            Year(2015)
+        }
+
+        fn day(&self) -> Day {
+            Day(1)
+        }
+
+        fn title(&self) -> &'static str {
+            "Sum"
+        }
+
+        fn parse(&self, input: &Input) -> PuzzleResult<Vec<i32>> {
+            Ok(input.lines()?.map(|l| l.parse().unwrap()).collect())
+        }
+
+        fn part1(&self, values: &Vec<i32>) -> PuzzleResult<Answer> {
+            Ok(values.iter().sum::<i32>().into())
+        }
+
+        fn part2(&self, values: &Vec<i32>) -> PuzzleResult<Answer> {
+            Ok(values.iter().product::<i32>().into())
+        }
+    }
+
+    struct Tagged;
+
+    impl Solution for Tagged {
+        type Parsed<'a> = ();
+
+        fn year(&self) -> Year {
+            Year(2015)
+        }
+
+        fn day(&self) -> Day {
+            Day(20)
+        }
+
+        fn title(&self) -> &'static str {
+            "Tagged"
+        }
+
+        fn tags(&self, part: Part) -> &'static [Tag] {
+            match part {
+                Part::One => &[Tag::Slow],
+                Part::Two => &[Tag::Slow, Tag::Broken],
+            }
+        }
+
+        fn parse(&self, _input: &Input) -> PuzzleResult<()> {
+            Ok(())
+        }
+
+        fn part1(&self, _parsed: &()) -> PuzzleResult<Answer> {
+            Ok(1.into())
+        }
+
+        fn part2(&self, _parsed: &()) -> PuzzleResult<Answer> {
+            Ok(2.into())
+        }
+    }
+
+    #[test]
+    fn test_skip() {
+        let skip = Skip::default();
+        assert_eq!(skip.reason(&Tagged, Part::One), None);
+        assert_eq!(skip.reason(&Tagged, Part::Two), Some(Tag::Broken));
+        assert_eq!(skip.select(&Tagged, None), Some(Some(Part::One)));
+        assert_eq!(skip.select(&Tagged, Some(Part::Two)), None);
+        assert_eq!(skip.select(&Sum, None), Some(None));
+
+        let skip = Skip::new(&[Tag::Slow], &[]);
+        assert_eq!(skip.reason(&Tagged, Part::Two), Some(Tag::Slow));
+        assert_eq!(skip.select(&Tagged, None), None);
+
+        let skip = Skip::new(&[], &[Tag::Broken]);
+        assert_eq!(skip.select(&Tagged, None), Some(None));
+    }
+
+    #[test]
+    fn test_solve() {
+        let puzzle: &dyn Puzzle = &Sum;
+        let input = Input::from("2\n3\n4\n");
+
+        let solved = puzzle.solve(&input, None).unwrap();
+        let answers: Vec<_> = solved.parts.iter().map(|p| p.answer.as_str()).collect();
+        assert_eq!(answers, vec!["9", "24"]);
+
+        let solved = puzzle.solve(&input, Some(Part::Two)).unwrap();
+        assert_eq!(solved.parts.len(), 1);
+        assert_eq!(solved.parts[0].part, Part::Two);
+        assert_eq!(solved.parts[0].answer, Answer::from(24));
+    }
+}
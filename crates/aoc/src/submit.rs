@@ -0,0 +1,178 @@
+use crate::{Answer, AocCache, Day, InputFetcher, Part, PuzzleError, PuzzleResult, Registry, Year};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// The reply to a submitted answer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    RateLimited {
+        wait: Option<String>,
+    },
+    /// The part is already solved, or not unlocked yet.
+    WrongLevel,
+    /// A reply that isn't recognized, as plain text.
+    Unknown(String),
+}
+
+impl Outcome {
+    /// Classifies the HTML page returned when posting an answer.
+    pub fn parse(html: &str) -> Outcome {
+        let text = article_text(html);
+
+        if text.contains("That's the right answer") {
+            Outcome::Right
+        } else if text.contains("You gave an answer too recently") {
+            let wait = text
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(wait, _)| wait.to_string());
+            Outcome::RateLimited { wait }
+        } else if text.contains("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Outcome::TooHigh
+            } else if text.contains("your answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            }
+        } else if text.contains("You don't seem to be solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            Outcome::Unknown(text)
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Right => write!(f, "That's the right answer!"),
+            Outcome::Wrong => write!(f, "That's not the right answer"),
+            Outcome::TooHigh => write!(f, "That's not the right answer, it is too high"),
+            Outcome::TooLow => write!(f, "That's not the right answer, it is too low"),
+            Outcome::RateLimited { wait: Some(wait) } => {
+                write!(f, "Answered too recently, {} left to wait", wait)
+            }
+            Outcome::RateLimited { wait: None } => write!(f, "Answered too recently"),
+            Outcome::WrongLevel => write!(f, "Already solved or not unlocked yet"),
+            Outcome::Unknown(text) => write!(f, "Unknown reply: {}", text),
+        }
+    }
+}
+
+/// The text of the `<article>` in the page, or of the whole page if there is none.
+fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(html, |(article, _)| article);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Submits the answer to a part, solving the puzzle first if no answer is given.
+///
+/// Answers that are already recorded are not submitted again.
+pub fn submit(
+    registry: &Registry,
+    cache: &AocCache,
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: Option<Answer>,
+) -> PuzzleResult<Outcome> {
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let puzzle = registry
+                .get(year, day)
+                .ok_or_else(|| PuzzleError::Input(format!("No puzzle for {year} day {day}")))?;
+            let input = cache.get_input(Year(year), Day(day))?;
+            let solved = puzzle.solve(&input, Some(part))?;
+            solved
+                .parts
+                .into_iter()
+                .next()
+                .map(|p| p.answer)
+                .ok_or_else(|| PuzzleError::Solution(format!("No answer for part {part}")))?
+        }
+    };
+
+    if let Some(recorded) = cache.answers(year, day)?.get(part) {
+        return Err(PuzzleError::Input(format!(
+            "Part {part} of {year} day {day} already has the answer {recorded}, not submitting {answer}"
+        )));
+    }
+
+    println!("Submitting {answer} for {year} day {day} part {part}");
+    cache.submit_answer(year, day, part, &answer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(article: &str) -> String {
+        format!("<html><body><main><article><p>{article}</p></article></main></body></html>")
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Outcome::parse(&page(
+                "That's the right answer! You are <em>one gold star</em> closer."
+            )),
+            Outcome::Right
+        );
+        assert_eq!(
+            Outcome::parse(&page(
+                "That's not the right answer; your answer is too high. Please wait one minute."
+            )),
+            Outcome::TooHigh
+        );
+        assert_eq!(
+            Outcome::parse(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            Outcome::parse(&page("That's not the right answer. If you're stuck...")),
+            Outcome::Wrong
+        );
+        assert_eq!(
+            Outcome::parse(&page(
+                "You gave an answer too recently. You have 4m 10s left to wait."
+            )),
+            Outcome::RateLimited {
+                wait: Some("4m 10s".into())
+            }
+        );
+        assert_eq!(
+            Outcome::parse(&page(
+                "You don't seem to be solving the right level. Did you already complete it?"
+            )),
+            Outcome::WrongLevel
+        );
+        assert_eq!(
+            Outcome::parse("<p>Hello</p>"),
+            Outcome::Unknown("Hello".into())
+        );
+    }
+}
@@ -0,0 +1,541 @@
+//! Frames of a grid that solutions draw as they go, played in the terminal
+//! or saved as an asciinema cast, a GIF or PNG images.
+//!
+//! Solutions call [`record`] with a closure that draws a [`Frame`]. The
+//! closure is only called while the runner records, i.e. with `--viz`, so
+//! drawing costs nothing otherwise:
+//!
+//! ```ignore
+//! while scene.step() {
+//!     viz::record(|| scene.frame());
+//! }
+//! ```
+
+use crate::grid::Pos;
+use crate::{Grid, PuzzleError, PuzzleResult};
+use clap::ValueEnum;
+use serde_json::json;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::ffi::OsString;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+/// At most this many frames are kept per part. When there are more, every
+/// second one is dropped, and then every fourth and so on, so that a long
+/// run is still shown from start to end.
+pub const MAX_FRAMES: usize = 500;
+
+/// The time each frame is shown.
+pub const FRAME_DELAY: Duration = Duration::from_millis(50);
+
+/// The side of a cell in pixels, in GIFs and PNGs.
+const CELL_SIZE: usize = 4;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Grey,
+}
+
+impl Colour {
+    const ALL: [Colour; 8] = [
+        Colour::Red,
+        Colour::Green,
+        Colour::Yellow,
+        Colour::Blue,
+        Colour::Magenta,
+        Colour::Cyan,
+        Colour::White,
+        Colour::Grey,
+    ];
+
+    fn rgb(self) -> [u8; 3] {
+        match self {
+            Colour::Red => [0xe0, 0x40, 0x40],
+            Colour::Green => [0x40, 0xc0, 0x40],
+            Colour::Yellow => [0xf0, 0xd0, 0x40],
+            Colour::Blue => [0x40, 0x70, 0xe0],
+            Colour::Magenta => [0xc0, 0x50, 0xc0],
+            Colour::Cyan => [0x40, 0xc0, 0xc0],
+            Colour::White => [0xf0, 0xf0, 0xf0],
+            Colour::Grey => [0x80, 0x80, 0x80],
+        }
+    }
+
+    fn ansi(self) -> u8 {
+        match self {
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Blue => 34,
+            Colour::Magenta => 35,
+            Colour::Cyan => 36,
+            Colour::White => 37,
+            Colour::Grey => 90,
+        }
+    }
+}
+
+/// A character, drawn in a colour or in the default one.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cell {
+    ch: char,
+    colour: Option<Colour>,
+}
+
+impl Cell {
+    pub fn plain(ch: char) -> Self {
+        Self { ch, colour: None }
+    }
+
+    pub fn coloured(ch: char, colour: Colour) -> Self {
+        Self {
+            ch,
+            colour: Some(colour),
+        }
+    }
+
+    /// The colour in images: its own, or white, or black for a space or a
+    /// dot, which are used for empty cells.
+    fn pixel(self) -> Option<Colour> {
+        match (self.colour, self.ch) {
+            (Some(colour), _) => Some(colour),
+            (None, ' ' | '.') => None,
+            (None, _) => Some(Colour::White),
+        }
+    }
+}
+
+impl From<char> for Cell {
+    fn from(ch: char) -> Self {
+        Cell::plain(ch)
+    }
+}
+
+/// A grid of cells with a caption below it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Frame {
+    grid: Grid<Cell>,
+    caption: String,
+}
+
+impl Frame {
+    /// An empty frame, filled with spaces.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            grid: Grid::new(width, height, Cell::plain(' ')),
+            caption: String::new(),
+        }
+    }
+
+    /// A frame with a cell for each cell of `grid`.
+    pub fn from_grid<T>(grid: &Grid<T>, cell: impl FnMut(&T) -> Cell) -> Self {
+        Self {
+            grid: grid.map(cell),
+            caption: String::new(),
+        }
+    }
+
+    pub fn with_caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = caption.into();
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    /// Sets the cell at `pos`. Positions outside the frame are left out.
+    pub fn set(&mut self, pos: Pos, cell: impl Into<Cell>) {
+        if let Some(c) = self.grid.get_mut(pos) {
+            *c = cell.into();
+        }
+    }
+
+    /// The frame with colours as ANSI escapes and `\r\n` line endings.
+    pub(crate) fn ansi(&self) -> String {
+        let mut out = String::new();
+        for row in self.grid.rows() {
+            let mut current = None;
+            for cell in row {
+                if cell.colour != current {
+                    match cell.colour {
+                        Some(colour) => out.push_str(&format!("\x1B[{}m", colour.ansi())),
+                        None => out.push_str("\x1B[0m"),
+                    }
+                    current = cell.colour;
+                }
+                out.push(cell.ch);
+            }
+            if current.is_some() {
+                out.push_str("\x1B[0m");
+            }
+            out.push_str("\r\n");
+        }
+        out.push_str(&self.caption);
+        out.push_str("\r\n");
+        out
+    }
+
+    /// The palette indices of the pixels, row by row.
+    fn pixels(&self) -> Vec<u8> {
+        let width = self.width() * CELL_SIZE;
+        let mut pixels = Vec::with_capacity(width * self.height() * CELL_SIZE);
+        for row in self.grid.rows() {
+            let line: Vec<u8> = row
+                .iter()
+                .flat_map(|cell| [palette_index(cell.pixel()); CELL_SIZE])
+                .collect();
+            for _ in 0..CELL_SIZE {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        pixels
+    }
+}
+
+/// The text of the frame, without colours.
+impl Display for Frame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for row in self.grid.rows() {
+            writeln!(f, "{}", row.iter().map(|cell| cell.ch).collect::<String>())?;
+        }
+        if !self.caption.is_empty() {
+            writeln!(f, "{}", self.caption)?;
+        }
+        Ok(())
+    }
+}
+
+fn palette_index(colour: Option<Colour>) -> u8 {
+    colour.map_or(0, |colour| {
+        Colour::ALL.iter().position(|&c| c == colour).unwrap() as u8 + 1
+    })
+}
+
+/// Black, for empty cells, followed by the colours.
+fn palette() -> Vec<u8> {
+    let mut palette = vec![0, 0, 0];
+    palette.extend(Colour::ALL.iter().flat_map(|colour| colour.rgb()));
+    palette
+}
+
+/// How recorded frames are shown.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Format {
+    /// Play them in the terminal
+    Terminal,
+    /// An asciinema cast
+    Cast,
+    /// An animated GIF
+    Gif,
+    /// A PNG image per frame
+    Png,
+}
+
+struct Recorder {
+    frames: Vec<Frame>,
+    seen: usize,
+    stride: usize,
+}
+
+impl Recorder {
+    fn new() -> Self {
+        Self {
+            frames: Vec::new(),
+            seen: 0,
+            stride: 1,
+        }
+    }
+
+    /// Counts a frame and tells whether it is kept.
+    fn wants(&mut self) -> bool {
+        let wanted = self.seen.is_multiple_of(self.stride);
+        self.seen += 1;
+        wanted
+    }
+
+    fn push(&mut self, frame: Frame) {
+        self.frames.push(frame);
+        if self.frames.len() >= MAX_FRAMES {
+            let mut i = 0;
+            self.frames.retain(|_| {
+                i += 1;
+                i % 2 == 1
+            });
+            self.stride *= 2;
+        }
+    }
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+/// Records the frames drawn on this thread from now on.
+pub fn start() {
+    RECORDER.set(Some(Recorder::new()));
+}
+
+/// Stops recording and returns the frames that were not taken.
+pub fn stop() -> Vec<Frame> {
+    RECORDER
+        .take()
+        .map(|recorder| recorder.frames)
+        .unwrap_or_default()
+}
+
+/// The frames recorded so far, which are then started over.
+pub fn take() -> Vec<Frame> {
+    RECORDER.with_borrow_mut(|recorder| match recorder {
+        Some(recorder) => std::mem::replace(recorder, Recorder::new()).frames,
+        None => Vec::new(),
+    })
+}
+
+/// Whether frames are recorded on this thread.
+pub fn enabled() -> bool {
+    RECORDER.with_borrow(Option::is_some)
+}
+
+/// Records the frame drawn by `draw`, which is only called when recording
+/// and when the frame is kept.
+pub fn record(draw: impl FnOnce() -> Frame) {
+    let wanted =
+        RECORDER.with_borrow_mut(|recorder| recorder.as_mut().is_some_and(Recorder::wants));
+    if wanted {
+        let frame = draw();
+        RECORDER.with_borrow_mut(|recorder| {
+            if let Some(recorder) = recorder {
+                recorder.push(frame);
+            }
+        });
+    }
+}
+
+/// Calls `f` and returns the frames it recorded.
+pub fn recording<R>(f: impl FnOnce() -> R) -> (R, Vec<Frame>) {
+    start();
+    let result = f();
+    (result, stop())
+}
+
+/// Plays the frames, redrawing the screen for each.
+pub fn play(frames: &[Frame], delay: Duration, out: &mut impl Write) -> io::Result<()> {
+    for frame in frames {
+        write!(out, "\x1B[H\x1B[2J{}", frame.ansi())?;
+        out.flush()?;
+        thread::sleep(delay);
+    }
+    Ok(())
+}
+
+/// Writes the frames as an asciinema cast, version 2.
+pub fn write_cast(frames: &[Frame], delay: Duration, out: &mut impl Write) -> io::Result<()> {
+    let width = frames.iter().map(Frame::width).max().unwrap_or(0);
+    let height = frames.iter().map(Frame::height).max().unwrap_or(0);
+    let header = json!({"version": 2, "width": width, "height": height + 1});
+    writeln!(out, "{}", header)?;
+
+    for (i, frame) in frames.iter().enumerate() {
+        let time = (delay * i as u32).as_secs_f64();
+        let clear = format!("\x1B[H\x1B[2J{}", frame.ansi());
+        writeln!(out, "{}", json!([time, "o", clear]))?;
+    }
+    Ok(())
+}
+
+/// Writes the frames as a looping GIF. The frames must be the same size.
+pub fn write_gif(frames: &[Frame], delay: Duration, out: impl Write) -> io::Result<()> {
+    let Some(first) = frames.first() else {
+        return Ok(());
+    };
+    let side = |cells: usize| {
+        u16::try_from(cells * CELL_SIZE)
+            .map_err(|_| io::Error::other("the frames are too large for a GIF"))
+    };
+    let (width, height) = (side(first.width())?, side(first.height())?);
+
+    let mut encoder =
+        gif::Encoder::new(out, width, height, &palette()).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+    let delay = (delay.as_millis() / 10).try_into().unwrap_or(u16::MAX);
+    for frame in frames {
+        if (side(frame.width())?, side(frame.height())?) != (width, height) {
+            return Err(io::Error::other("the frames have different sizes"));
+        }
+        let pixels = frame.pixels();
+        let gif_frame = gif::Frame {
+            width,
+            height,
+            delay,
+            buffer: Cow::Borrowed(&pixels),
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&gif_frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+/// Writes one frame as a PNG image.
+pub fn write_png(frame: &Frame, out: impl Write) -> io::Result<()> {
+    let width = (frame.width() * CELL_SIZE) as u32;
+    let height = (frame.height() * CELL_SIZE) as u32;
+    let mut encoder = png::Encoder::new(out, width, height);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(palette());
+
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer
+        .write_image_data(&frame.pixels())
+        .map_err(io::Error::other)
+}
+
+/// Saves the frames next to `base`, e.g. `06.part1.gif` for `06.part1`,
+/// and returns where. PNGs go in a directory named `base`, one per frame,
+/// and frames for the terminal are played instead.
+pub fn export(frames: &[Frame], format: Format, base: &Path) -> PuzzleResult<PathBuf> {
+    let with_extension = |extension: &str| {
+        let mut name = OsString::from(base.as_os_str());
+        name.push(".");
+        name.push(extension);
+        PathBuf::from(name)
+    };
+    let create = |path: &Path| {
+        let error = |error| PuzzleError::IO {
+            msg: format!("Can't write {}", path.display()),
+            error,
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(error)?;
+        }
+        File::create(path).map(BufWriter::new).map_err(error)
+    };
+    let written = |path: &Path, result: io::Result<()>| {
+        result.map_err(|error| PuzzleError::IO {
+            msg: format!("Can't write {}", path.display()),
+            error,
+        })
+    };
+
+    match format {
+        Format::Terminal => {
+            play(frames, FRAME_DELAY, &mut io::stdout())?;
+            Ok(base.to_path_buf())
+        }
+        Format::Cast => {
+            let path = with_extension("cast");
+            let mut out = create(&path)?;
+            written(&path, write_cast(frames, FRAME_DELAY, &mut out))?;
+            written(&path, out.flush())?;
+            Ok(path)
+        }
+        Format::Gif => {
+            let path = with_extension("gif");
+            written(&path, write_gif(frames, FRAME_DELAY, create(&path)?))?;
+            Ok(path)
+        }
+        Format::Png => {
+            for (i, frame) in frames.iter().enumerate() {
+                let path = base.join(format!("{:04}.png", i + 1));
+                written(&path, write_png(frame, create(&path)?))?;
+            }
+            Ok(base.to_path_buf())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> Frame {
+        let grid = Grid::parse("#.\n.#\n", |ch| ch).unwrap();
+        let mut frame = Frame::from_grid(&grid, |&ch| match ch {
+            '#' => Cell::coloured('#', Colour::Grey),
+            ch => Cell::plain(ch),
+        });
+        frame.set((0, 1), Cell::coloured('@', Colour::Red));
+        frame.set((5, 5), '!');
+        frame.with_caption("Step 1")
+    }
+
+    #[test]
+    fn test_frame() {
+        let frame = frame();
+        assert_eq!(frame.to_string(), "#@\n.#\nStep 1\n");
+        assert_eq!(
+            frame.ansi(),
+            "\x1B[90m#\x1B[31m@\x1B[0m\r\n.\x1B[90m#\x1B[0m\r\nStep 1\r\n"
+        );
+        assert_eq!(frame.pixels().len(), 2 * 2 * CELL_SIZE * CELL_SIZE);
+        assert_eq!(frame.pixels()[CELL_SIZE], palette_index(Some(Colour::Red)));
+    }
+
+    #[test]
+    fn test_record() {
+        record(|| panic!("Drawn without recording"));
+
+        let ((), frames) = recording(|| {
+            for i in 0..MAX_FRAMES * 2 {
+                record(|| Frame::new(1, 1).with_caption(i.to_string()));
+            }
+        });
+        assert_eq!(frames.len(), MAX_FRAMES / 2);
+        assert_eq!(frames[1].caption, "4");
+        assert_eq!(
+            frames.last().unwrap().caption,
+            (MAX_FRAMES * 2 - 4).to_string()
+        );
+        assert!(!enabled());
+    }
+
+    #[test]
+    fn test_write() {
+        let frames = [frame(), frame()];
+
+        let mut cast = Vec::new();
+        write_cast(&frames, Duration::from_millis(500), &mut cast).unwrap();
+        let cast = String::from_utf8(cast).unwrap();
+        let lines: Vec<_> = cast.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], r#"{"height":3,"version":2,"width":2}"#);
+        assert!(lines[2].starts_with(r#"[0.5,"o","\u001b[H"#));
+
+        let mut gif = Vec::new();
+        write_gif(&frames, FRAME_DELAY, &mut gif).unwrap();
+        let mut decoder = gif::DecodeOptions::new().read_info(&gif[..]).unwrap();
+        assert_eq!(decoder.width() as usize, 2 * CELL_SIZE);
+        let mut count = 0;
+        while decoder.read_next_frame().unwrap().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 2);
+
+        let mut png = Vec::new();
+        write_png(&frames[0], &mut png).unwrap();
+        let reader = png::Decoder::new(&png[..]).read_info().unwrap();
+        assert_eq!(reader.info().size(), (8, 8));
+    }
+}
@@ -0,0 +1,81 @@
+// Each test crate uses its own subset of these helpers
+#![allow(dead_code)]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread;
+use std::thread::JoinHandle;
+
+/// A reply from the fake server: the status code and the body.
+pub type Reply = (u16, String);
+
+/// A fake adventofcode.com that serves one request per reply, in order, and
+/// hands back the requests it received.
+pub fn serve(replies: Vec<Reply>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = thread::spawn(move || {
+        let mut requests = Vec::new();
+
+        for (status, body) in replies {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+
+            let reason = match status {
+                200 => "OK",
+                302 => "Found",
+                _ => "Error",
+            };
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {status} {reason}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+
+            requests.push(request);
+        }
+
+        requests
+    });
+
+    (url, handle)
+}
+
+pub fn ok(body: String) -> Reply {
+    (200, body)
+}
+
+/// The reply to the session check done before the first download.
+pub fn settings() -> Reply {
+    ok("<html><body>Settings</body></html>".into())
+}
+
+/// A puzzle page with the given `<article>` contents.
+pub fn page(article: &str) -> String {
+    format!(
+        "<html><body><main><article class=\"day-desc\">{article}</article></main></body></html>"
+    )
+}
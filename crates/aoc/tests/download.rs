@@ -0,0 +1,147 @@
+use aoc::{AocCache, Clock, PuzzleError, Retry};
+use common::{ok, serve, settings};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+mod common;
+
+fn cache(root: &Path, url: &str) -> AocCache {
+    AocCache::new(root)
+        .with_base_url(url)
+        .with_session("abc123")
+        .with_retry(Retry {
+            retries: 2,
+            delay: Duration::from_millis(1),
+        })
+}
+
+fn cached_files(root: &Path) -> Vec<String> {
+    match fs::read_dir(root.join("aoc").join("2024")) {
+        Ok(entries) => entries
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+#[test]
+fn test_server_errors_are_retried() {
+    let root = tempfile::tempdir().unwrap();
+    let (url, server) = serve(vec![
+        settings(),
+        (500, "Oops".into()),
+        (502, "Bad gateway".into()),
+        ok("1 2 3\n".into()),
+    ]);
+    let cache = cache(root.path(), &url);
+
+    let path = cache.get_path(2024, 1).unwrap();
+    assert_eq!(fs::read_to_string(path).unwrap(), "1 2 3\n");
+    assert_eq!(cached_files(root.path()), vec!["01.txt"]);
+
+    let requests = server.join().unwrap();
+    assert!(requests[1..]
+        .iter()
+        .all(|request| request.starts_with("GET /2024/day/1/input HTTP/1.1\r\n")));
+}
+
+#[test]
+fn test_retries_give_up() {
+    let root = tempfile::tempdir().unwrap();
+    let (url, server) = serve(vec![
+        settings(),
+        (500, "Oops".into()),
+        (500, "Oops".into()),
+        (500, "Oops".into()),
+    ]);
+    let cache = cache(root.path(), &url);
+
+    let result = cache.get_path(2024, 1);
+    assert!(matches!(result, Err(PuzzleError::DownloadFailed(..))));
+    assert!(cached_files(root.path()).is_empty());
+    server.join().unwrap();
+}
+
+#[test]
+fn test_locked_input_is_not_cached() {
+    let root = tempfile::tempdir().unwrap();
+    let (url, server) = serve(vec![
+        settings(),
+        (
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time.".into(),
+        ),
+    ]);
+    let cache = cache(root.path(), &url);
+
+    let result = cache.get_path(2024, 25);
+    assert!(matches!(result, Err(PuzzleError::NotUnlocked(_))));
+    assert!(cached_files(root.path()).is_empty());
+    server.join().unwrap();
+}
+
+#[test]
+fn test_login_page_is_not_cached() {
+    let root = tempfile::tempdir().unwrap();
+    let (url, server) = serve(vec![
+        settings(),
+        ok("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n".into()),
+    ]);
+    let cache = cache(root.path(), &url);
+
+    let result = cache.get_path(2024, 1);
+    assert!(matches!(result, Err(PuzzleError::InvalidSession(_))));
+    assert!(cached_files(root.path()).is_empty());
+    server.join().unwrap();
+}
+
+/// A clock that only moves when slept on.
+#[derive(Debug)]
+struct FakeClock(Mutex<SystemTime>);
+
+impl Clock for FakeClock {
+    fn now(&self) -> SystemTime {
+        *self.0.lock().unwrap()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        *self.0.lock().unwrap() += duration;
+    }
+}
+
+/// 2024-12-06T04:59:00Z, a minute before day 6 unlocks.
+fn before_day_6() -> Arc<FakeClock> {
+    Arc::new(FakeClock(Mutex::new(
+        UNIX_EPOCH + Duration::from_secs(1733461140),
+    )))
+}
+
+#[test]
+fn test_early_fetch_is_refused() {
+    let root = tempfile::tempdir().unwrap();
+    let cache = cache(root.path(), "http://127.0.0.1:1").with_clock(before_day_6());
+
+    match cache.get_path(2024, 6) {
+        Err(PuzzleError::NotUnlocked(msg)) => assert!(msg.contains("unlocks in 1m 0s")),
+        result => panic!("Unexpected {result:?}"),
+    }
+    assert!(cache
+        .get_path(2024, 5)
+        .is_err_and(|e| !matches!(e, PuzzleError::NotUnlocked(_))));
+}
+
+#[test]
+fn test_wait_for_unlock() {
+    let root = tempfile::tempdir().unwrap();
+    let (url, server) = serve(vec![settings(), ok("1 2 3\n".into())]);
+    let clock = before_day_6();
+    let cache = cache(root.path(), &url)
+        .with_clock(clock.clone())
+        .with_wait(true);
+
+    cache.get_path(2024, 6).unwrap();
+    assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_secs(1733461200));
+    server.join().unwrap();
+}
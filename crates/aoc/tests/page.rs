@@ -0,0 +1,90 @@
+use aoc::{Answer, AocCache, Outcome, Part, PuzzleError, Retry};
+use common::{ok, page, serve, settings};
+use std::fs;
+use std::time::Duration;
+
+mod common;
+
+#[test]
+fn test_samples_are_cached() {
+    let root = tempfile::tempdir().unwrap();
+
+    let (url, server) = serve(vec![
+        settings(),
+        ok(page(
+            "<h2>--- Day 6: Guard Gallivant ---</h2>\
+         <pre><code>....#.....\n.^........\n</code></pre>\
+         <pre><code>#.\n</code></pre>",
+        )),
+    ]);
+    let cache = AocCache::new(root.path())
+        .with_base_url(&url)
+        .with_session("abc123")
+        .with_retry(Retry {
+            retries: 0,
+            delay: Duration::ZERO,
+        });
+
+    let path = cache.get_sample_path(2024, 6, 2).unwrap();
+    assert_eq!(fs::read_to_string(path).unwrap(), "#.\n");
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("GET /settings HTTP/1.1\r\n"));
+    let request = &requests[1];
+    assert!(request.starts_with("GET /2024/day/6 HTTP/1.1\r\n"));
+
+    // Served from the cache. The server is gone by now, so a download would
+    // fail with another error than the missing sample.
+    let path = cache.get_sample_path(2024, 6, 1).unwrap();
+    assert_eq!(
+        fs::read_to_string(path).unwrap(),
+        "....#.....\n.^........\n"
+    );
+    for _ in 0..2 {
+        assert!(cache
+            .description(2024, 6)
+            .unwrap()
+            .contains("Day 6: Guard Gallivant"));
+        let error = cache.get_sample_path(2024, 6, 3).unwrap_err();
+        assert!(matches!(error, PuzzleError::Input(_)), "{error}");
+        assert!(error.to_string().contains("There are 2 samples"), "{error}");
+    }
+}
+
+#[test]
+fn test_page_is_fetched_again_when_part_2_unlocks() {
+    let root = tempfile::tempdir().unwrap();
+
+    let part1 = "<h2>--- Day 6: Guard Gallivant ---</h2><pre><code>#.\n</code></pre>";
+    let (url, server) = serve(vec![
+        settings(),
+        ok(page(part1)),
+        ok(page(
+            "That's the right answer! You are <em>one gold star</em> closer.",
+        )),
+        // The second part is another article on the page
+        ok(page(&format!(
+            "{part1}</article><article class=\"day-desc\">\
+             <h2>--- Part Two ---</h2><pre><code>.#\n</code></pre>"
+        ))),
+    ]);
+    let cache = AocCache::new(root.path())
+        .with_base_url(&url)
+        .with_session("abc123");
+
+    assert!(cache.get_sample_path(2024, 6, 2).is_err());
+    assert!(!cache.description(2024, 6).unwrap().contains("Part Two"));
+
+    let outcome = cache
+        .submit_answer(2024, 6, Part::One, &Answer::from(41))
+        .unwrap();
+    assert_eq!(outcome, Outcome::Right);
+
+    let path = cache.get_sample_path(2024, 6, 2).unwrap();
+    assert_eq!(fs::read_to_string(path).unwrap(), ".#\n");
+    assert!(cache.description(2024, 6).unwrap().contains("Part Two"));
+
+    let requests = server.join().unwrap();
+    assert_eq!(requests.len(), 4);
+    assert!(requests[3].starts_with("GET /2024/day/6 HTTP/1.1\r\n"));
+}
@@ -0,0 +1,63 @@
+use aoc::scaffold::{new_day, Workspace};
+use aoc::{AocCache, Retry};
+use common::{ok, page, serve, settings};
+use std::fs;
+use std::time::Duration;
+
+mod common;
+
+const LIB: &str = "\
+#[cfg(test)]
+mod e00;
+mod e05;
+mod e07;
+";
+
+#[test]
+fn test_new_day() {
+    let root = tempfile::tempdir().unwrap();
+    fs::write(root.path().join("Cargo.toml"), "[workspace]\n").unwrap();
+    let src = root.path().join("crates/aoc24/src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("lib.rs"), LIB).unwrap();
+
+    let (url, server) = serve(vec![
+        settings(),
+        ok(page(
+            "<h2>--- Day 6: Guard Gallivant ---</h2>\
+             <pre><code>....#.....\n.^........\n</code></pre>",
+        )),
+        ok("#..\n".into()),
+    ]);
+    let cache = AocCache::new(root.path().join("cache"))
+        .with_base_url(&url)
+        .with_session("abc123")
+        .with_retry(Retry {
+            retries: 0,
+            delay: Duration::ZERO,
+        });
+
+    let workspace = Workspace::find(&src).unwrap();
+    new_day(&cache, &workspace, 2024, 6).unwrap();
+
+    let requests = server.join().unwrap();
+    assert!(requests[1].starts_with("GET /2024/day/6 HTTP/1.1\r\n"));
+    assert!(requests[2].starts_with("GET /2024/day/6/input HTTP/1.1\r\n"));
+
+    let module = fs::read_to_string(src.join("e06.rs")).unwrap();
+    assert!(module.contains("pub struct GuardGallivant;"));
+    assert!(module.contains("(year = 2024, day = 6, title = \"Guard Gallivant\")"));
+    assert!(module.contains("const SAMPLE: &str = \"\\\n....#.....\n.^........\n\";"));
+
+    let lib = fs::read_to_string(src.join("lib.rs")).unwrap();
+    assert!(lib.contains("mod e05;\nmod e06;\nmod e07;\n"));
+
+    // Everything is cached by now, and the work on the day is kept
+    fs::write(src.join("e06.rs"), "// Work in progress\n").unwrap();
+    new_day(&cache, &workspace, 2024, 6).unwrap();
+    assert_eq!(
+        fs::read_to_string(src.join("e06.rs")).unwrap(),
+        "// Work in progress\n"
+    );
+    assert_eq!(fs::read_to_string(src.join("lib.rs")).unwrap(), lib);
+}
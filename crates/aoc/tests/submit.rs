@@ -0,0 +1,110 @@
+use aoc::{Answer, AocCache, Outcome, Part, PuzzleError, Verdict};
+use common::{ok, page, serve, settings};
+use std::path::Path;
+
+mod common;
+
+fn cache(root: &Path, url: &str) -> AocCache {
+    AocCache::new(root)
+        .with_base_url(url)
+        .with_session("abc123")
+}
+
+#[test]
+fn test_right_answer_is_recorded() {
+    let root = tempfile::tempdir().unwrap();
+    let (url, server) = serve(vec![
+        settings(),
+        ok(page(
+            "That's the right answer! You are <em>one gold star</em> closer.",
+        )),
+    ]);
+    let cache = cache(root.path(), &url);
+
+    let answer = Answer::from(4665);
+    let outcome = cache.submit_answer(2024, 6, Part::One, &answer).unwrap();
+    assert_eq!(outcome, Outcome::Right);
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("GET /settings HTTP/1.1\r\n"));
+    let request = &requests[1];
+    assert!(request.starts_with("POST /2024/day/6/answer HTTP/1.1\r\n"));
+    assert!(request.contains("Cookie: session=abc123\r\n"));
+    assert!(request.ends_with("\r\n\r\nlevel=1&answer=4665"));
+
+    let answers = cache.answers(2024, 6).unwrap();
+    assert_eq!(answers.check(Part::One, &answer), Verdict::Correct);
+    assert_eq!(answers.check(Part::Two, &answer), Verdict::Unknown);
+}
+
+#[test]
+fn test_wrong_answer_is_not_recorded() {
+    let root = tempfile::tempdir().unwrap();
+    let (url, server) = serve(vec![
+        settings(),
+        ok(page(
+            "That's not the right answer; your answer is too high.",
+        )),
+    ]);
+    let cache = cache(root.path(), &url);
+
+    let outcome = cache
+        .submit_answer(2015, 1, Part::Two, &Answer::from(1784))
+        .unwrap();
+    assert_eq!(outcome, Outcome::TooHigh);
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("GET /settings HTTP/1.1\r\n"));
+    let request = &requests[1];
+    assert!(request.starts_with("POST /2015/day/1/answer HTTP/1.1\r\n"));
+    assert!(request.ends_with("level=2&answer=1784"));
+    assert_eq!(cache.answers(2015, 1).unwrap(), Default::default());
+}
+
+#[test]
+fn test_rate_limited() {
+    let root = tempfile::tempdir().unwrap();
+    let (url, server) = serve(vec![
+        settings(),
+        ok(page(
+            "You gave an answer too recently. You have 38s left to wait.",
+        )),
+    ]);
+    let cache = cache(root.path(), &url);
+
+    let outcome = cache
+        .submit_answer(2024, 6, Part::One, &Answer::from(12))
+        .unwrap();
+    assert_eq!(
+        outcome,
+        Outcome::RateLimited {
+            wait: Some("38s".into())
+        }
+    );
+    assert_eq!(server.join().unwrap().len(), 2);
+}
+
+#[test]
+fn test_recorded_answer_is_not_submitted() {
+    let root = tempfile::tempdir().unwrap();
+    let cache = cache(root.path(), "http://127.0.0.1:1");
+
+    let mut answers = cache.answers(2024, 6).unwrap();
+    answers.set(Part::One, Answer::from(4665));
+    cache.record_answers(2024, 6, &answers).unwrap();
+
+    let registry = aoc::Registry::new();
+    let result = aoc::submit(&registry, &cache, 2024, 6, Part::One, Some(4665.into()));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_expired_session_is_rejected() {
+    let root = tempfile::tempdir().unwrap();
+    let (url, server) = serve(vec![(302, String::new())]);
+    let cache = cache(root.path(), &url);
+
+    let result = cache.submit_answer(2024, 6, Part::One, &Answer::from(12));
+    assert!(matches!(result, Err(PuzzleError::InvalidSession(_))));
+    assert_eq!(server.join().unwrap().len(), 1);
+}
@@ -1,21 +1,25 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, PuzzleResult};
+use aoc::{Answer, Input, PuzzleError, PuzzleResult, Solution};
 
-const DAY: Day = Day(1);
+pub struct NotQuiteLisp;
 
-#[allow(clippy::result_large_err)]
-pub fn not_quite_lisp(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "Not Quite Lisp");
-    let input = aoc.get_input(YEAR, DAY)?;
-    let input = input.read_to_string()?;
+#[aoc::solution(year = 2015, day = 1, title = "Not Quite Lisp")]
+impl Solution for NotQuiteLisp {
+    type Parsed<'a> = &'a str;
 
-    let floor_count = count_floors(&input);
-    println!("aoc15e01a: {}", floor_count);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let steps = find_basement(&input).unwrap();
-    println!("aoc15e01b: {}", steps);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(count_floors(input).into())
+    }
 
-    Ok(floor_count == 232 && steps == 1783)
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        let steps = find_basement(input).ok_or(PuzzleError::Solution(
+            "The basement is never entered".into(),
+        ))?;
+        Ok(steps.into())
+    }
 }
 
 fn count_floors(input: &str) -> i32 {
@@ -1,10 +1,7 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, PuzzleResult};
-
-const DAY: Day = Day(2);
+use aoc::{Answer, Input, PuzzleResult, Solution};
 
 #[derive(Debug, PartialEq)]
-struct Package {
+pub struct Package {
     length: u32,
     width: u32,
     height: u32,
@@ -50,17 +47,23 @@ impl Package {
     }
 }
 
-pub fn i_was_told_there_would_be_no_math(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "I Was Told there Would Be No Math");
-    let input = aoc.get_input(YEAR, DAY)?;
+pub struct IWasToldThereWouldBeNoMath;
+
+#[aoc::solution(year = 2015, day = 2, title = "I Was Told there Would Be No Math")]
+impl Solution for IWasToldThereWouldBeNoMath {
+    type Parsed<'a> = Vec<Package>;
+
+    fn parse(&self, input: &Input) -> PuzzleResult<Vec<Package>> {
+        Ok(parse(input.text()?))
+    }
 
-    let packages = parse(&input.read_to_string()?);
-    let area: u32 = packages.iter().map(|p| p.area()).sum();
-    let ribbon: u32 = packages.iter().map(|p| p.ribbon()).sum();
-    println!("aoc15e02a: {}", area);
-    println!("aoc15e02b: {}", ribbon);
+    fn part1(&self, input: &Vec<Package>) -> PuzzleResult<Answer> {
+        Ok(input.iter().map(|p| p.area()).sum::<u32>().into())
+    }
 
-    Ok(area == 1588178 && ribbon == 3783758)
+    fn part2(&self, input: &Vec<Package>) -> PuzzleResult<Answer> {
+        Ok(input.iter().map(|p| p.ribbon()).sum::<u32>().into())
+    }
 }
 
 fn parse(body: &str) -> Vec<Package> {
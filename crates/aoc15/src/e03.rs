@@ -1,21 +1,23 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, PuzzleResult};
+use aoc::{Answer, Direction, Input, Point, PuzzleResult, Solution};
 use std::{collections::HashSet, iter};
 
-const DAY: Day = Day(3);
+pub struct PerfectlySphericalHousesInAVacuum;
 
-pub fn perfectly_spherical_houses_in_a_vacuum(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "Perfectly Spherical Houses in a Vacuum");
+#[aoc::solution(year = 2015, day = 3, title = "Perfectly Spherical Houses in a Vacuum")]
+impl Solution for PerfectlySphericalHousesInAVacuum {
+    type Parsed<'a> = &'a str;
 
-    let input = aoc.get_input(YEAR, DAY)?.read_to_string()?;
-
-    let house_count = walk(&input);
-    println!("aoc15e03a: {}", house_count);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let robo_count = walk_with_robo(&input);
-    println!("aoc15e03b: {}", robo_count);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(walk(input).into())
+    }
 
-    Ok(house_count == 2565 && robo_count == 2639)
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(walk_with_robo(input).into())
+    }
 }
 
 fn walk(input: &str) -> usize {
@@ -29,21 +31,15 @@ fn walk_with_robo(input: &str) -> usize {
     santa_houses.union(&robo_houses).count()
 }
 
-fn houses_visited(input: &str) -> HashSet<(i32, i32)> {
-    let start = (0, 0);
+fn houses_visited(input: &str) -> HashSet<Point> {
+    let start = Point::ORIGIN;
 
     input
         .chars()
         .scan(start, |state, ch| {
-            let (x, y) = state;
-            *state = match ch {
-                'v' => (*x, *y + 1),
-                '^' => (*x, *y - 1),
-                '<' => (*x - 1, *y),
-                '>' => (*x + 1, *y),
-                _ => panic!("Unknown direction: '{}'", ch),
-            };
-
+            let direction =
+                Direction::try_from(ch).unwrap_or_else(|_| panic!("Unknown direction: '{}'", ch));
+            *state += direction;
             Some(*state)
         })
         .chain(iter::once(start))
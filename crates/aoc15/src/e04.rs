@@ -1,28 +1,32 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, PuzzleError, PuzzleResult};
+use aoc::{Answer, Input, PuzzleError, PuzzleResult, Solution};
 use rayon::iter::ParallelIterator;
 use rayon::prelude::ParallelBridge;
 use std::sync::{Arc, Mutex};
 use std::thread;
 
-const DAY: Day = Day(4);
+pub struct TheIdealStockingStuffer;
 
-pub fn the_ideal_stocking_stuffer(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "The Ideal Stocking Stuffer");
+#[aoc::solution(year = 2015, day = 4, title = "The Ideal Stocking Stuffer")]
+impl Solution for TheIdealStockingStuffer {
+    type Parsed<'a> = &'a str;
 
-    let input = aoc.get_input(YEAR, DAY)?.read_to_string()?;
-
-    let m = find_match_threaded(input.trim(), 5).ok_or(PuzzleError::Solution(
-        "No match found for 5 leading zeroes".into(),
-    ))?;
-    println!("aoc15e04a: {}", m);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        Ok(input.text()?.trim())
+    }
 
-    let m2 = find_match_threaded(input.trim(), 6).ok_or(PuzzleError::Solution(
-        "No match found for 6 leading zeroes".into(),
-    ))?;
-    println!("aoc15e04b: {}", m2);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        let m = find_match_threaded(input, 5).ok_or(PuzzleError::Solution(
+            "No match found for 5 leading zeroes".into(),
+        ))?;
+        Ok(m.into())
+    }
 
-    Ok(m == 117946 && m2 == 3938038)
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        let m = find_match_threaded(input, 6).ok_or(PuzzleError::Solution(
+            "No match found for 6 leading zeroes".into(),
+        ))?;
+        Ok(m.into())
+    }
 }
 
 #[allow(dead_code)]
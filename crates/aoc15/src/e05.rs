@@ -1,22 +1,23 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, Lines, PuzzleResult};
+use aoc::{Answer, Input, Lines, PuzzleResult, Solution};
 use fancy_regex::Regex;
 
-const DAY: Day = Day(5);
+pub struct DoesntHeHaveInternElvesForThis;
 
-pub fn doesnt_he_have_intern_elves_for_this(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "Doesn't He Have Intern-Elves For This?");
-    let input = aoc.get_input(YEAR, DAY)?;
-    // .read_to_string()
-    // .map_err(|e| PuzzleError::Input(format!("Failed to read the input for day {DAY}: {e}")))?;
+#[aoc::solution(year = 2015, day = 5, title = "Doesn't He Have Intern-Elves For This?")]
+impl Solution for DoesntHeHaveInternElvesForThis {
+    type Parsed<'a> = &'a str;
 
-    let nice_count = count_nice_ones(input.lines()?)?;
-    println!("aoc15e05a: {nice_count}");
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let really_nice_count = count_really_nice_ones(input.lines()?)?;
-    println!("aoc15e05b: {really_nice_count}");
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(count_nice_ones((*input).into())?.into())
+    }
 
-    Ok(nice_count == 255 && really_nice_count == 55)
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(count_really_nice_ones((*input).into())?.into())
+    }
 }
 
 fn count_nice_ones(reader: Lines) -> PuzzleResult<usize> {
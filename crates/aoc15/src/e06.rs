@@ -1,25 +1,21 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, PuzzleResult};
+use aoc::{Answer, Input, PuzzleResult, Solution};
 use fancy_regex::Regex;
 
-const DAY: Day = Day(6);
+pub struct ProbablyAFireHazard;
 
-pub fn probably_a_fire_hazard(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "Probably a Fire Hazard");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2015, day = 6, title = "Probably a Fire Hazard", tags = [slow])]
+impl Solution for ProbablyAFireHazard {
+    type Parsed<'a> = &'a str;
 
-    #[cfg(feature = "EXCLUDE_SLOW_SOLUTIONS")]
-    {
-        println!("Skipping...");
-        return Ok(true);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
     }
 
-    #[cfg(not(feature = "EXCLUDE_SLOW_SOLUTIONS"))]
-    {
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
         let mut grid = LightGrid::new();
 
-        for line in input.lines()? {
-            let instruction = Instruction::parse(&line);
+        for line in input.lines() {
+            let instruction = Instruction::parse(line);
             match instruction {
                 Instruction::TurnOn(tl, br) => grid.turn_on(tl, br),
                 Instruction::TurnOff(tl, br) => grid.turn_off(tl, br),
@@ -27,13 +23,14 @@ pub fn probably_a_fire_hazard(aoc: &AocCache) -> PuzzleResult<bool> {
             }
         }
 
-        let count = grid.count_on();
-        println!("aoc15e06a: {}", count);
+        Ok(grid.count_on().into())
+    }
 
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
         let mut grid = LightGrid2::new();
 
-        for line in input.lines()? {
-            let instruction = Instruction::parse(&line);
+        for line in input.lines() {
+            let instruction = Instruction::parse(line);
             match instruction {
                 Instruction::TurnOn(tl, br) => grid.turn_on(tl, br),
                 Instruction::TurnOff(tl, br) => grid.turn_off(tl, br),
@@ -41,10 +38,7 @@ pub fn probably_a_fire_hazard(aoc: &AocCache) -> PuzzleResult<bool> {
             }
         }
 
-        let sum = grid.sum();
-        println!("aoc15e06b: {}", sum);
-
-        Ok(count == 400410 && sum == 15343601)
+        Ok(grid.sum().into())
     }
 }
 
@@ -1,60 +1,53 @@
 use crate::e07::Operand::{Value, Wire};
 use crate::e07::Operation::{And, Forward, LShift, Not, Or, RShift};
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, PuzzleResult};
-use regex::{Match, Regex};
+use aoc::{parse, Answer, Input, PuzzleError, PuzzleResult, Solution};
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::rc::Rc;
 
-const DAY: Day = Day(7);
+pub struct SomeAssemblyRequired;
 
-pub fn some_assembly_required(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "Some Assembly Required");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2015, day = 7, title = "Some Assembly Required", tags = [slow])]
+impl Solution for SomeAssemblyRequired {
+    type Parsed<'a> = &'a str;
 
-    #[cfg(feature = "EXCLUDE_SLOW_SOLUTIONS")]
-    {
-        println!("Skipping...");
-        return Ok(true);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
     }
 
-    #[cfg(not(feature = "EXCLUDE_SLOW_SOLUTIONS"))]
-    {
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
         let mut circuit = Circuit::new();
 
-        for line in input.lines()? {
-            let gate = Gate::parse(&line);
+        for gate in parse::lines(input, Gate::parse)? {
             circuit.add_gate(gate);
         }
 
-        let a1 = match circuit.eval("a") {
-            Some(result) => {
-                println!("aoc15e07a: {}", result);
-                result
-            }
-            None => return Ok(false),
-        };
+        let a1 = circuit
+            .eval("a")
+            .ok_or(PuzzleError::Solution("No signal on wire a".into()))?;
+        Ok(a1.into())
+    }
+
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        let a1 = self.part1(input)?;
+        let a1 = a1
+            .as_str()
+            .parse()
+            .map_err(|_| PuzzleError::Solution(format!("Invalid signal: {a1}")))?;
 
         let mut circuit = Circuit::new();
 
-        for line in input.lines()? {
-            let mut gate = Gate::parse(&line);
+        for mut gate in parse::lines(input, Gate::parse)? {
             if gate.operation == Forward && gate.output == "b" {
                 gate.inputs = vec![Value(a1)]
             }
             circuit.add_gate(gate);
         }
 
-        let a2 = match circuit.eval("a") {
-            Some(result) => {
-                println!("aoc15e07b: {}", result);
-                result
-            }
-            None => return Ok(false),
-        };
-
-        Ok(a1 == 16076 && a2 == 2797)
+        let a2 = circuit
+            .eval("a")
+            .ok_or(PuzzleError::Solution("No signal on wire a".into()))?;
+        Ok(a2.into())
     }
 }
 
@@ -104,47 +97,31 @@ impl Gate {
         }
     }
 
-    fn parse(s: &str) -> Self {
-        fn match_as_str(m: Option<Match>) -> &str {
-            m.unwrap().as_str()
-        }
+    fn parse(s: &str) -> PuzzleResult<Self> {
+        let fields = parse::scan("{} -> {}", s)?;
+        let wire = fields.str(1).to_string();
+
+        let gate = match fields.str(0).split(' ').collect::<Vec<_>>()[..] {
+            [operand] => Self::new(Forward, vec![operand.into()], wire),
+            ["NOT", operand] => Self::new(Not, vec![operand.into()], wire),
+            [operand_a, operation, operand_b] => {
+                let operation = match operation {
+                    "AND" => And,
+                    "OR" => Or,
+                    "LSHIFT" => LShift,
+                    "RSHIFT" => RShift,
+                    _ => {
+                        return Err(PuzzleError::Input(format!(
+                            "Invalid binary operation: {operation}"
+                        )))
+                    }
+                };
+                Self::new(operation, vec![operand_a.into(), operand_b.into()], wire)
+            }
+            _ => return Err(PuzzleError::Input(format!("Can't parse instruction: {s}"))),
+        };
 
-        let direct_pattern = Regex::new(r"^(\w+) -> (\w+)$").unwrap();
-        let unary_pattern = Regex::new(r"^(NOT) (\w+) -> (\w+)$").unwrap();
-        let binary_pattern = Regex::new(r"^(\w+) (AND|OR|LSHIFT|RSHIFT) (\w+) -> (\w+)$").unwrap();
-
-        if let Some(cs) = direct_pattern.captures(s) {
-            let operand = match_as_str(cs.get(1)).into();
-            let wire = match_as_str(cs.get(2)).into();
-            Self::new(Forward, vec![operand], wire)
-        } else if let Some(cs) = unary_pattern.captures(s) {
-            let operation = match_as_str(cs.get(1));
-            let operation = match operation {
-                "NOT" => Not,
-                _ => panic!("Invalid unary operation: {operation}"),
-            };
-            let operand = match_as_str(cs.get(2)).into();
-            let wire = match_as_str(cs.get(3)).into();
-            return Self::new(operation, vec![operand], wire);
-        } else if let Some(cs) = binary_pattern.captures(s) {
-            let operand_a = match_as_str(cs.get(1)).into();
-            let operand_b = match_as_str(cs.get(3)).into();
-
-            let operation = match_as_str(cs.get(2));
-            let operation = match operation {
-                "AND" => And,
-                "OR" => Or,
-                "LSHIFT" => LShift,
-                "RSHIFT" => RShift,
-                _ => panic!("Invalid unary operation: {operation}"),
-            };
-
-            let wire = match_as_str(cs.get(4)).into();
-
-            return Self::new(operation, vec![operand_a, operand_b], wire);
-        } else {
-            panic!("Can't parse instruction: {s}");
-        }
+        Ok(gate)
     }
 
     fn compute(&self, wires: &HashMap<String, WireValue>) -> Option<WireValue> {
@@ -236,3 +213,33 @@ impl Display for Circuit {
         write!(f, "}}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+123 -> x
+456 -> y
+x AND y -> d
+x OR y -> e
+x LSHIFT 2 -> f
+y RSHIFT 2 -> g
+NOT x -> h
+NOT y -> i
+";
+
+    #[test]
+    fn test_circuit() {
+        let mut circuit = Circuit::new();
+        for line in SAMPLE.lines() {
+            circuit.add_gate(Gate::parse(line).unwrap());
+        }
+
+        assert_eq!(circuit.eval("d"), Some(72));
+        assert_eq!(circuit.eval("f"), Some(492));
+        assert_eq!(circuit.eval("g"), Some(114));
+        assert_eq!(circuit.eval("i"), Some(65079));
+        assert!(Gate::parse("x XOR y -> z").is_err());
+    }
+}
@@ -1,19 +1,22 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, Lines, PuzzleError, PuzzleResult};
+use aoc::{Answer, Input, Lines, PuzzleError, PuzzleResult, Solution};
 
-const DAY: Day = Day(8);
+pub struct Matchsticks;
 
-pub fn matchsticks(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "Matchsticks");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2015, day = 8, title = "Matchsticks")]
+impl Solution for Matchsticks {
+    type Parsed<'a> = &'a str;
 
-    let decoded_delta = decode_delta(input.lines()?)?;
-    println!("aoc15e08a: {}", decoded_delta);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let encoded_delta = encode_delta(input.lines()?)?;
-    println!("aoc15e08b: {}", encoded_delta);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(decode_delta((*input).into())?.into())
+    }
 
-    Ok(decoded_delta == 1371 && encoded_delta == 2117)
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(encode_delta((*input).into())?.into())
+    }
 }
 
 fn decode_delta(lines: Lines) -> PuzzleResult<usize> {
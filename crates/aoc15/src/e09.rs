@@ -1,21 +1,24 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, Lines, PuzzleError, PuzzleResult};
+use aoc::{Answer, Input, Lines, PuzzleError, PuzzleResult, Solution};
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
-const DAY: Day = Day(9);
+pub struct AllInASingleNight;
 
-pub fn all_in_a_single_night(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "All in a Single Night");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2015, day = 9, title = "All in a Single Night")]
+impl Solution for AllInASingleNight {
+    type Parsed<'a> = &'a str;
 
-    let shortest = shortest_path(input.lines()?)?;
-    println!("aoc15e09a: {}", shortest);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let longest = longest_path(input.lines()?)?;
-    println!("aoc15e09b: {}", longest);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(shortest_path((*input).into())?.into())
+    }
 
-    Ok(shortest == 207 && longest == 804)
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(longest_path((*input).into())?.into())
+    }
 }
 
 type Cities = HashSet<String>;
@@ -1,32 +1,32 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, PuzzleResult};
+use aoc::{Answer, Input, PuzzleResult, Solution};
 
-const DAY: Day = Day(10);
+pub struct ElvesLookElvesSay;
 
-pub fn elves_look_elves_say(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "Elves Look, Elves Say");
+#[aoc::solution(year = 2015, day = 10, title = "Elves Look, Elves Say")]
+impl Solution for ElvesLookElvesSay {
+    type Parsed<'a> = &'a str;
 
-    let mut input = aoc
-        .get_input(YEAR, DAY)?
-        .read_to_string()?
-        .trim()
-        .to_string();
-
-    for _ in 0..40 {
-        input = look_say(&input)?;
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        Ok(input.text()?.trim())
     }
 
-    let len_40 = input.len();
-    println!("aoc15e10a: {}", len_40);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        let mut s = input.to_string();
+        for _ in 0..40 {
+            s = look_say(&s)?;
+        }
 
-    for _ in 0..10 {
-        input = look_say(&input)?;
+        Ok(s.len().into())
     }
 
-    let len_50 = input.len();
-    println!("aoc15e10b: {}", len_50);
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        let mut s = input.to_string();
+        for _ in 0..50 {
+            s = look_say(&s)?;
+        }
 
-    Ok(len_40 == 360154 && len_50 == 5103798)
+        Ok(s.len().into())
+    }
 }
 
 fn look_say(s: &str) -> PuzzleResult<String> {
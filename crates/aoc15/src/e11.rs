@@ -1,24 +1,22 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, PuzzleResult};
+use aoc::{Answer, Input, PuzzleResult, Solution};
 
-const DAY: Day = Day(11);
+pub struct CorporatePolicy;
 
-pub fn corporate_policy(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "Corporate Policy");
+#[aoc::solution(year = 2015, day = 11, title = "Corporate Policy")]
+impl Solution for CorporatePolicy {
+    type Parsed<'a> = &'a str;
 
-    let input = aoc
-        .get_input(YEAR, DAY)?
-        .read_to_string()?
-        .trim()
-        .to_string();
-
-    let next_password = next_pw(&input);
-    println!("aoc15e11a: {}", next_password);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        Ok(input.text()?.trim())
+    }
 
-    let second_next_password = next_pw(&next_password);
-    println!("aoc15e11b: {}", second_next_password);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(next_pw(input).into())
+    }
 
-    Ok(next_password == "cqjxxyzz" && second_next_password == "cqkaabcc")
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(next_pw(&next_pw(input)).into())
+    }
 }
 
 const A_CHAR: u8 = b'a';
@@ -1,39 +1,28 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, PuzzleError, PuzzleResult};
-use regex::Regex;
+use aoc::{parse, Answer, Input, PuzzleError, PuzzleResult, Solution};
 use std::iter::Peekable;
 use std::str::Chars;
 
-const DAY: Day = Day(12);
+pub struct JsAbacusFrameworkIo;
 
-pub fn js_abacus_framework_io(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "JSAbacusFramework.io");
-    let input = aoc
-        .get_input(YEAR, DAY)?
-        .read_to_string()?
-        .trim()
-        .to_string();
+#[aoc::solution(year = 2015, day = 12, title = "JSAbacusFramework.io")]
+impl Solution for JsAbacusFrameworkIo {
+    type Parsed<'a> = &'a str;
 
-    let sum = sum_numbers(&input)?;
-    println!("aoc15e12a: {sum}");
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        Ok(input.text()?.trim())
+    }
 
-    let red_sum = dummy_parse(&input)?;
-    println!("aoc15e12b: {red_sum}");
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(sum_numbers(input)?.into())
+    }
 
-    Ok(sum == 191164 && red_sum == 87842)
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(dummy_parse(input)?.into())
+    }
 }
 
 fn sum_numbers(input: &str) -> Result<i64, PuzzleError> {
-    let non_digit_pattern =
-        Regex::new(r"[^-\d]+").expect("Failed to compile non-digit pattern regex");
-
-    let sum: i64 = non_digit_pattern
-        .split(input)
-        .filter(|s| !s.is_empty())
-        .map(|n| n.parse::<i64>().unwrap_or_default())
-        .sum();
-
-    Ok(sum)
+    Ok(parse::ints(input)?.into_iter().sum())
 }
 
 struct DummyParser<'a> {
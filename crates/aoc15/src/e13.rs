@@ -1,20 +1,26 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, Lines, PuzzleError, PuzzleResult};
+use aoc::{Answer, Input, Lines, PuzzleError, PuzzleResult, Solution};
 use itertools::Itertools;
 use std::cmp::max;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
-const DAY: Day = Day(13);
+pub struct KnightsOfTheDinnerTable;
 
-pub fn knights_of_the_dinner_table(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "Knights of the Dinner Table");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2015, day = 13, title = "Knights of the Dinner Table")]
+impl Solution for KnightsOfTheDinnerTable {
+    type Parsed<'a> = &'a str;
 
-    let p1 = part_1b(input.lines()?)?;
-    let p2 = part_2b(input.lines()?)?;
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
+
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part_1b((*input).into())?.into())
+    }
 
-    Ok(p1 == 618 && p2 == 601)
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part_2b((*input).into())?.into())
+    }
 }
 
 #[allow(dead_code)]
@@ -171,7 +177,7 @@ fn sum_happiness_b(pairs: &HashMap<(Rc<str>, Rc<str>), i64>, v: Vec<Rc<str>>) ->
 fn parse_entries(
     lines: Box<dyn Iterator<Item = PuzzleResult<String>>>,
 ) -> PuzzleResult<Vec<((String, String), i64)>> {
-    Ok(lines.map(|e| parse_line(&e.unwrap()).unwrap()).collect())
+    lines.map(|line| parse_line(&line?)).collect()
 }
 
 type EntriesB = Vec<((Rc<str>, Rc<str>), i64)>;
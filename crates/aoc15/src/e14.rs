@@ -1,30 +1,30 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, PuzzleResult};
+use aoc::{parse, Answer, Input, PuzzleResult, Solution};
 use itertools::Itertools;
-use regex::Regex;
 use std::cmp::max;
 use std::collections::HashMap;
 
-const DAY: Day = Day(14);
+pub struct ReindeerOlympics;
 
-pub fn reindeer_olympics(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "Reindeer Olympics");
+#[aoc::solution(year = 2015, day = 14, title = "Reindeer Olympics")]
+impl Solution for ReindeerOlympics {
+    type Parsed<'a> = &'a str;
 
-    let input = aoc.get_input(YEAR, DAY)?.read_to_string()?;
-
-    let max_distance = part_1(&input)?;
-    println!("aoc15e14a: {}", max_distance);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let score = part_2(&input)?;
-    println!("aoc15e14b: {}", score);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part_1(input)?.into())
+    }
 
-    Ok(max_distance == 2696 && score == 1084)
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part_2(input)?.into())
+    }
 }
 
 fn part_1(input: &str) -> PuzzleResult<u32> {
-    let max_distance = input
-        .lines()
-        .map(Reindeer::from)
+    let max_distance = parse::lines(input, Reindeer::parse)?
+        .iter()
         .map(|reindeer| reindeer.distance_after(2503))
         .fold(0, max);
 
@@ -32,7 +32,7 @@ fn part_1(input: &str) -> PuzzleResult<u32> {
 }
 
 fn part_2(input: &str) -> PuzzleResult<u32> {
-    let reindeers = input.lines().map(Reindeer::from).collect::<Vec<_>>();
+    let reindeers = parse::lines(input, Reindeer::parse)?;
 
     let mut scores = HashMap::<&str, u32>::new();
 
@@ -75,27 +75,18 @@ impl Reindeer {
         }
     }
 
-    fn from(s: &str) -> Self {
-        let pattern = Regex::new(
-            r"(\w+) can fly (\d+) km/s for (\d+) seconds, but then must rest for (\d+) seconds.",
-        )
-        .unwrap();
-
-        if let Some(caps) = pattern.captures(s) {
-            let name = caps.get(1).unwrap().as_str().to_string();
-            let speed = caps.get(2).unwrap().as_str().parse().unwrap();
-            let fly_time = caps.get(3).unwrap().as_str().parse().unwrap();
-            let rest_time = caps.get(4).unwrap().as_str().parse().unwrap();
-
-            Reindeer {
-                name,
-                speed,
-                fly_time,
-                rest_time,
-            }
-        } else {
-            panic!("Invalid input: {}", s);
-        }
+    fn parse(s: &str) -> PuzzleResult<Self> {
+        let fields = parse::scan(
+            "{} can fly {} km/s for {} seconds, but then must rest for {} seconds.",
+            s,
+        )?;
+
+        Ok(Reindeer {
+            name: fields.str(0).to_string(),
+            speed: fields.get(1)?,
+            fly_time: fields.get(2)?,
+            rest_time: fields.get(3)?,
+        })
     }
 
     fn name(&self) -> &str {
@@ -118,14 +109,16 @@ mod tests {
 
     #[test]
     fn can_parse_inout() {
-        let reindeer = Reindeer::from(
+        let reindeer = Reindeer::parse(
             "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.",
-        );
+        )
+        .unwrap();
         assert_eq!(reindeer, Reindeer::new("Comet".to_string(), 14, 10, 127));
 
-        let reindeer = Reindeer::from(
+        let reindeer = Reindeer::parse(
             "Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.",
-        );
+        )
+        .unwrap();
         assert_eq!(reindeer, Reindeer::new("Dancer".to_string(), 16, 11, 162));
     }
 
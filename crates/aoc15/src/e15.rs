@@ -1,26 +1,23 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, PuzzleResult};
-use regex::Regex;
+use aoc::{parse, Answer, Input, PuzzleResult, Solution};
 use std::cmp::max;
-use std::sync::LazyLock;
 
-const DAY: Day = Day(15);
+pub struct ScienceForHungryPeople;
 
-pub fn science_for_hungry_people(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "Science for Hungry People");
+#[aoc::solution(year = 2015, day = 15, title = "Science for Hungry People")]
+impl Solution for ScienceForHungryPeople {
+    type Parsed<'a> = Vec<Ingredient>;
 
-    let mut ingredients = Vec::<Ingredient>::new();
-    for line in aoc.get_input(YEAR, DAY)?.lines()? {
-        ingredients.push(Ingredient::parse(&line));
+    fn parse(&self, input: &Input) -> PuzzleResult<Vec<Ingredient>> {
+        parse::lines(input.text()?, Ingredient::parse)
     }
 
-    let max_score = get_max_score(&ingredients, false);
-    let max_restricted_score = get_max_score(&ingredients, true);
-
-    println!("aoc15e15a: {}", max_score);
-    println!("aoc15e15b: {}", max_restricted_score);
+    fn part1(&self, input: &Vec<Ingredient>) -> PuzzleResult<Answer> {
+        Ok(get_max_score(input, false).into())
+    }
 
-    Ok(max_score == 21367368 || max_restricted_score == 1766400)
+    fn part2(&self, input: &Vec<Ingredient>) -> PuzzleResult<Answer> {
+        Ok(get_max_score(input, true).into())
+    }
 }
 
 fn get_max_score(ingredients: &[Ingredient], exactly_500: bool) -> i64 {
@@ -73,7 +70,7 @@ fn generate_combinations<F: FnMut(&[i64])>(n: usize, max: i64, mut callback: F)
 }
 
 #[derive(Debug)]
-struct Ingredient {
+pub struct Ingredient {
     capacity: i64,
     durability: i64,
     flavor: i64,
@@ -81,11 +78,6 @@ struct Ingredient {
     calories: i64,
 }
 
-static INGREDIENTS_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-    let re = r"(\w+): capacity (-?\d+), durability (-?\d+), flavor (-?\d+), texture (-?\d+), calories (-?\d+)";
-    Regex::new(re).unwrap()
-});
-
 impl Ingredient {
     #[allow(dead_code)] // Used for tests
     fn new(capacity: i64, durability: i64, flavor: i64, texture: i64, calories: i64) -> Ingredient {
@@ -98,24 +90,16 @@ impl Ingredient {
         }
     }
 
-    fn parse(s: &str) -> Ingredient {
-        if let Some(caps) = INGREDIENTS_REGEX.captures(s) {
-            let capacity = caps.get(2).unwrap().as_str().parse().unwrap();
-            let durability = caps.get(3).unwrap().as_str().parse().unwrap();
-            let flavor = caps.get(4).unwrap().as_str().parse().unwrap();
-            let texture = caps.get(5).unwrap().as_str().parse().unwrap();
-            let calories = caps.get(6).unwrap().as_str().parse().unwrap();
-
-            Ingredient {
-                capacity,
-                durability,
-                flavor,
-                texture,
-                calories,
-            }
-        } else {
-            panic!("Invalid input: {}", s);
-        }
+    fn parse(s: &str) -> PuzzleResult<Ingredient> {
+        let [capacity, durability, flavor, texture, calories] = parse::ints_n(s)?;
+
+        Ok(Ingredient {
+            capacity,
+            durability,
+            flavor,
+            texture,
+            calories,
+        })
     }
 }
 
@@ -127,7 +111,8 @@ mod test {
     fn test_ingredient_parse() {
         let i = Ingredient::parse(
             "Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8",
-        );
+        )
+        .unwrap();
 
         assert_eq!(i.capacity, -1);
         assert_eq!(i.durability, -2);
@@ -1,14 +1,31 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, Input, InputFetcher, PuzzleResult};
+use aoc::{Answer, Input, PuzzleResult, Solution};
 use std::collections::BTreeMap;
 
-const DAY: Day = Day(16);
+pub struct AuntSue;
 
-pub fn aunt_sue(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "Aunt Sue");
+#[aoc::solution(year = 2015, day = 16, title = "Aunt Sue")]
+impl Solution for AuntSue {
+    type Parsed<'a> = Vec<Sue>;
 
-    let sues = parse(&aoc.get_input(YEAR, DAY)?)?;
-    let tape = Sue {
+    fn parse(&self, input: &Input) -> PuzzleResult<Vec<Sue>> {
+        parse(input)
+    }
+
+    fn part1(&self, input: &Vec<Sue>) -> PuzzleResult<Answer> {
+        let sues: Vec<_> = input.iter().filter(|&s| tape().matches(s)).collect();
+        assert_eq!(sues.len(), 1);
+        Ok(sues[0].id.into())
+    }
+
+    fn part2(&self, input: &Vec<Sue>) -> PuzzleResult<Answer> {
+        let sues: Vec<_> = input.iter().filter(|&s| tape().really_matches(s)).collect();
+        assert_eq!(sues.len(), 1);
+        Ok(sues[0].id.into())
+    }
+}
+
+fn tape() -> Sue {
+    Sue {
         id: 0,
         props: BTreeMap::from([
             (SueProp::Children, 3),
@@ -22,19 +39,7 @@ pub fn aunt_sue(aoc: &AocCache) -> PuzzleResult<bool> {
             (SueProp::Cars, 2),
             (SueProp::Perfumes, 1),
         ]),
-    };
-
-    let matching_sues: Vec<_> = sues.iter().filter(|&s| tape.matches(s)).collect();
-    assert_eq!(matching_sues.len(), 1);
-    let matching_sue = matching_sues.first().unwrap();
-    println!("aoc15e16a: {:?}", matching_sue.id);
-
-    let really_matching_sues: Vec<_> = sues.iter().filter(|&s| tape.really_matches(s)).collect();
-    assert_eq!(really_matching_sues.len(), 1);
-    let really_matching_sue = really_matching_sues.first().unwrap();
-    println!("aoc15e16b: {:?}", really_matching_sue.id);
-
-    Ok(matching_sue.id == 213 && really_matching_sue.id == 323)
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -73,7 +78,7 @@ where
 }
 
 #[derive(Debug, PartialEq)]
-struct Sue {
+pub struct Sue {
     id: usize,
     props: BTreeMap<SueProp, usize>,
 }
@@ -82,12 +87,12 @@ impl Sue {
     fn matches(&self, other: &Sue) -> bool {
         self.props
             .iter()
-            .all(|(prop, value)| other.props.get(prop).map_or(true, |v| *v == *value))
+            .all(|(prop, value)| other.props.get(prop).is_none_or(|v| *v == *value))
     }
 
     fn really_matches(&self, other: &Sue) -> bool {
         self.props.iter().all(|(prop, value)| {
-            other.props.get(prop).map_or(true, |v| match prop {
+            other.props.get(prop).is_none_or(|v| match prop {
                 SueProp::Cats | SueProp::Trees => *v > *value,
                 SueProp::Pomeranians | SueProp::Goldfish => *v < *value,
                 _ => *v == *value,
@@ -1,27 +1,28 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, PuzzleResult};
+use aoc::{Answer, Input, PuzzleResult, Solution};
 
-const DAY: Day = Day(17);
+pub struct NoSuchThingAsTooMuch;
 
-pub fn no_such_thing_as_too_much(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "No Such Thing as Too Much");
+#[aoc::solution(year = 2015, day = 17, title = "No Such Thing as Too Much")]
+impl Solution for NoSuchThingAsTooMuch {
+    type Parsed<'a> = Vec<i32>;
 
-    let input = aoc
-        .get_input(YEAR, DAY)?
-        .read_to_string()?
-        .lines()
-        .map(|l| l.parse::<i32>().unwrap())
-        .collect::<Vec<_>>();
-
-    let combo_count = pack_count(&input, 150);
-    println!("aoc15e17a: {:?}", combo_count);
+    fn parse(&self, input: &Input) -> PuzzleResult<Vec<i32>> {
+        Ok(input
+            .text()?
+            .lines()
+            .map(|l| l.parse::<i32>().unwrap())
+            .collect())
+    }
 
-    let combos = pack(&input, 150);
-    let min_len = combos.iter().map(|c| c.len()).min().unwrap();
-    let min_count = combos.iter().filter(|c| c.len() == min_len).count();
-    println!("aoc15e17b: {:?}", min_count);
+    fn part1(&self, input: &Vec<i32>) -> PuzzleResult<Answer> {
+        Ok(pack_count(input, 150).into())
+    }
 
-    Ok(combo_count == 654 && min_count == 57)
+    fn part2(&self, input: &Vec<i32>) -> PuzzleResult<Answer> {
+        let combos = pack(input, 150);
+        let min_len = combos.iter().map(|c| c.len()).min().unwrap();
+        Ok(combos.iter().filter(|c| c.len() == min_len).count().into())
+    }
 }
 
 fn pack_count(containers: &[i32], target: i32) -> i32 {
@@ -1,78 +1,85 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, PuzzleResult};
-use std::cmp::min;
+use aoc::sim::Simulation;
+use aoc::viz::{Cell, Colour, Frame};
+use aoc::{sim, Answer, Input, Part, PuzzleResult, Solution};
 use std::fmt::Display;
 
-const DAY: Day = Day(18);
+pub struct LikeAGifForYourYard;
 
-pub fn like_a_gif_for_your_yard(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "Like a GIF For Your Yard");
+#[aoc::solution(year = 2015, day = 18, title = "Like a GIF For Your Yard")]
+impl Solution for LikeAGifForYourYard {
+    type Parsed<'a> = &'a str;
 
-    let input = aoc.get_input(YEAR, DAY)?.read_to_string()?;
-
-    let mut grid = Grid::from(input.as_ref());
-    for _ in 0..100 {
-        grid.step();
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
     }
 
-    let part_1_count = grid.count();
-    println!("aoc15e18a: {}", part_1_count);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        let mut grid = Grid::from(*input);
+        for _ in 0..100 {
+            grid.step();
+        }
 
-    let mut grid = Grid::from(input.as_ref());
-    for _ in 0..100 {
-        grid.step_2();
+        Ok(grid.count().into())
     }
 
-    let part_2_count = grid.count();
-    println!("aoc15e18a: {}", part_2_count);
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        let mut grid = Grid::from(*input);
+        for _ in 0..100 {
+            grid.step_2();
+        }
 
-    Ok(part_1_count == 821 && part_2_count == 886)
+        Ok(grid.count().into())
+    }
+
+    fn debug(&self, input: &&str, part: Part) -> PuzzleResult<()> {
+        sim::debug(Lights {
+            grid: Grid::from(*input),
+            part,
+        })
+    }
 }
 
+#[derive(Clone)]
 struct Grid {
-    grid: Vec<Vec<char>>,
+    grid: aoc::Grid<char>,
 }
 
 impl Grid {
-    fn new(grid: Vec<Vec<char>>) -> Self {
+    fn new(grid: aoc::Grid<char>) -> Self {
         Self { grid }
     }
 
     fn count(&self) -> usize {
-        self.grid.iter().flatten().filter(|&&c| c == '#').count()
+        self.grid.iter().filter(|(_, &c)| c == '#').count()
     }
 
     fn step(&mut self) {
         let mut next = self.grid.clone();
 
-        for (y, row) in self.grid.iter().enumerate() {
-            for (x, &cell) in row.iter().enumerate() {
-                next[y][x] = Self::get_next(self.count_neighbours(x, y), cell);
-            }
+        for ((y, x), &cell) in self.grid.iter() {
+            next[(y, x)] = Self::get_next(self.count_neighbours(x, y), cell);
         }
 
         self.grid = next;
     }
 
     fn step_2(&mut self) {
-        let max_y = self.grid.len() - 1;
-        let max_x = self.grid[max_y].len() - 1;
+        let max_y = self.grid.height() - 1;
+        let max_x = self.grid.width() - 1;
 
-        self.grid[0][0] = '#';
-        self.grid[0][max_x] = '#';
-        self.grid[max_y][0] = '#';
-        self.grid[max_y][max_x] = '#';
+        self.grid[(0, 0)] = '#';
+        self.grid[(0, max_x)] = '#';
+        self.grid[(max_y, 0)] = '#';
+        self.grid[(max_y, max_x)] = '#';
 
         let mut next = self.grid.clone();
 
-        for (y, row) in self.grid.iter().enumerate() {
-            for (x, &cell) in row.iter().enumerate() {
-                next[y][x] = if self.is_corner(x, y) {
-                    '#'
-                } else {
-                    Self::get_next(self.count_neighbours(x, y), cell)
-                };
-            }
+        for ((y, x), &cell) in self.grid.iter() {
+            next[(y, x)] = if self.is_corner(x, y) {
+                '#'
+            } else {
+                Self::get_next(self.count_neighbours(x, y), cell)
+            };
         }
 
         self.grid = next;
@@ -96,62 +103,62 @@ impl Grid {
     }
 
     fn is_corner(&self, x: usize, y: usize) -> bool {
-        (x == 0 && (y == 0 || y == self.grid.len() - 1))
-            || (x == self.grid.len() - 1 && (y == 0 || y == self.grid.len() - 1))
+        (x == 0 || x == self.grid.width() - 1) && (y == 0 || y == self.grid.height() - 1)
     }
 
     fn count_neighbours(&self, x: usize, y: usize) -> usize {
-        assert!(!self.grid.is_empty());
-        assert!(x < self.grid.len());
-
-        let begin_x = if x == 0 { 0 } else { x - 1 };
-        let end_x = min(self.grid.len() - 1, x + 1);
-        let begin_y = if y == 0 { 0 } else { y - 1 };
-        let end_y = min(self.grid[x].len() - 1, y + 1);
-
-        let mut count = 0;
-        for y_neighbour in begin_y..=end_y {
-            assert!(!self.grid[y].is_empty());
-            assert!(y < self.grid[y].len());
-            for x_neighbour in begin_x..=end_x {
-                if (x_neighbour != x || y_neighbour != y)
-                    && self.grid[y_neighbour][x_neighbour] == '#'
-                {
-                    count += 1;
-                }
-            }
+        self.grid
+            .neighbours8((y, x))
+            .filter(|&pos| self.grid[pos] == '#')
+            .count()
+    }
+}
+
+/// The grid animated as in one of the parts, where part 2 has the corners
+/// stuck on.
+#[derive(Clone)]
+struct Lights {
+    grid: Grid,
+    part: Part,
+}
+
+impl Simulation for Lights {
+    fn step(&mut self) -> bool {
+        match self.part {
+            Part::One => self.grid.step(),
+            Part::Two => self.grid.step_2(),
         }
+        true
+    }
+
+    fn frame(&self) -> Frame {
+        Frame::from_grid(&self.grid.grid, |&c| match c {
+            '#' => Cell::coloured('#', Colour::Yellow),
+            c => Cell::plain(c),
+        })
+    }
 
-        count
+    fn inspect(&self) -> String {
+        format!("{} lights on", self.grid.count())
     }
 }
 
 impl From<&str> for Grid {
     fn from(s: &str) -> Self {
-        let grid = s
-            .lines()
-            .filter(|l| !l.is_empty())
-            .map(|l| l.chars().collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-
-        Self::new(grid)
+        Self::new(aoc::Grid::parse(s, |c| c).expect("Rows of different lengths"))
     }
 }
+
 impl Display for Grid {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let str = self
-            .grid
-            .iter()
-            .map(|l| l.iter().collect::<String>())
-            .collect::<Vec<_>>()
-            .join("\n");
-        write!(f, "{}", str)
+        write!(f, "{}", self.grid)
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc::sim::Debugger;
 
     #[test]
     fn test_neighbour_count() {
@@ -202,4 +209,17 @@ mod test {
 
         assert_eq!(grid.count(), 17);
     }
+
+    #[test]
+    fn test_lights() {
+        let grid = Grid::from(".#.#.#\n...##.\n#....#\n..#...\n#.#..#\n####..\n");
+        let mut debugger = Debugger::new(Lights {
+            grid,
+            part: Part::One,
+        });
+        assert!(debugger.run_until(10, |lights| lights.inspect() == "4 lights on"));
+        assert_eq!(debugger.step(), 3);
+        debugger.back(3);
+        assert_eq!(debugger.state().inspect(), "15 lights on");
+    }
 }
@@ -1,34 +1,44 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, Lines, PuzzleError, PuzzleResult};
+use aoc::{Answer, Input, Lines, PuzzleError, PuzzleResult, Solution};
 use itertools::Itertools;
 use rand::seq::SliceRandom;
 use std::collections::HashSet;
 
-const DAY: Day = Day(19);
+pub struct MedicineForRudolph;
 
-pub fn medicine_for_rudolph(aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "Medicine for Rudolph");
+#[aoc::solution(
+    year = 2015,
+    day = 19,
+    title = "Medicine for Rudolph",
+    part2_tags = [broken]
+)]
+impl Solution for MedicineForRudolph {
+    type Parsed<'a> = (Vec<(String, String)>, String);
 
-    let (rules, molecule) = parse(aoc.get_input(YEAR, DAY)?.lines()?)?;
-    // println!("input: {:?}", input);
-    let rule_set = rules
-        .iter()
-        .map(|(a, b)| (a.as_str(), b.as_str()))
-        .collect::<Vec<_>>();
+    fn parse(&self, input: &Input) -> PuzzleResult<(Vec<(String, String)>, String)> {
+        parse(input.lines()?)
+    }
 
-    let ms = create_molecules(&molecule, &rule_set);
-    println!("aoc15e19: {}", ms.len());
+    fn part1(&self, input: &(Vec<(String, String)>, String)) -> PuzzleResult<Answer> {
+        let (rules, molecule) = input;
+        let rule_set = rule_set(rules);
+
+        Ok(create_molecules(molecule, &rule_set).len().into())
+    }
 
-    #[cfg(feature = "BrokenSolutions")]
-    {
+    fn part2(&self, input: &(Vec<(String, String)>, String)) -> PuzzleResult<Answer> {
+        let (rules, molecule) = input;
+        let rule_set = rule_set(rules);
         let n = min_reductions_bisect_with_persistent_tracking(&rule_set, molecule.trim());
-        println!(
-            "aoc15e19b: {}",
-            n.map(|x| x.to_string()).unwrap_or("None".to_string())
-        );
+        n.map(Answer::from)
+            .ok_or_else(|| PuzzleError::Solution("The molecule can't be made".into()))
     }
+}
 
-    Ok(true)
+fn rule_set(rules: &[(String, String)]) -> Vec<(&str, &str)> {
+    rules
+        .iter()
+        .map(|(a, b)| (a.as_str(), b.as_str()))
+        .collect()
 }
 
 fn parse(lines: Lines) -> PuzzleResult<(Vec<(String, String)>, String)> {
@@ -178,7 +188,6 @@ fn min_reductions_to_e(rules: &[(&str, &str)], target: &str) -> u32 {
     count
 }
 
-#[cfg(feature = "BrokenSolutions")]
 fn min_reductions_bisect_with_persistent_tracking(
     rules: &[(&str, &str)],
     target: &str,
@@ -205,7 +214,6 @@ fn min_reductions_bisect_with_persistent_tracking(
     depth_limited_search_with_persistent_tracking(&reversed_rules, target, low, &mut visited)
 }
 
-#[cfg(feature = "BrokenSolutions")]
 fn depth_limited_search_with_persistent_tracking(
     rules: &[(&str, &str)],
     molecule: &str,
@@ -1,21 +1,29 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, PuzzleResult};
+use aoc::{Answer, Input, PuzzleError, PuzzleResult, Solution};
 
-const DAY: Day = Day(20);
+pub struct InfiniteElvesAndInfiniteHouses;
 
-pub fn infinite_elves_and_infinite_houses(_aoc: &AocCache) -> PuzzleResult<bool> {
-    head(YEAR, DAY, "Infinite Elves and Infinite Houses");
+#[aoc::solution(
+    year = 2015,
+    day = 20,
+    title = "Infinite Elves and Infinite Houses",
+    tags = [broken]
+)]
+impl Solution for InfiniteElvesAndInfiniteHouses {
+    type Parsed<'a> = ();
 
-    #[cfg(feature = "BrokenSolutions")]
-    {
-        let p1 = part_1(33100000);
-        println!("aoc15e20a: {}", p1);
+    fn parse(&self, _input: &Input) -> PuzzleResult<()> {
+        Ok(())
     }
 
-    Ok(true)
+    fn part1(&self, _input: &()) -> PuzzleResult<Answer> {
+        Ok(part_1(33100000).into())
+    }
+
+    fn part2(&self, _input: &()) -> PuzzleResult<Answer> {
+        Err(PuzzleError::Solution("Not solved yet".into()))
+    }
 }
 
-#[cfg(feature = "BrokenSolutions")]
 fn part_1(limit: u64) -> u64 {
     (1u64..)
         .map(|n| {
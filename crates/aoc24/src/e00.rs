@@ -1,22 +1,22 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, Input, InputFetcher, PuzzleResult};
+use aoc::{Answer, Input, PuzzleResult, Solution};
 
-const DAY: Day = Day(0);
+pub struct Foo;
 
-#[allow(dead_code)]
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Foo");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2024, day = 0, title = "Foo")]
+impl Solution for Foo {
+    type Parsed<'a> = &'a str;
 
-    let p1 = part1(&input)?;
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 0);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let p2 = part2(&input)?;
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 0);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part1(&(*input).into())?.into())
+    }
 
-    Ok(())
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part2(&(*input).into())?.into())
+    }
 }
 
 fn part1(_input: &Input) -> PuzzleResult<i32> {
@@ -31,15 +31,17 @@ fn part2(_input: &Input) -> PuzzleResult<i32> {
 mod tests {
     use super::*;
 
-    const SAMPLE: &str = "";
-
     #[test]
+    #[ignore = "needs the puzzle page in the cache"]
     fn test_part1() {
-        assert_eq!(part1(&SAMPLE.into()).unwrap(), 0);
+        let sample = Input::sample(2024, 0, 1).unwrap();
+        assert_eq!(part1(&sample).unwrap(), 0);
     }
 
     #[test]
+    #[ignore = "needs the puzzle page in the cache"]
     fn test_part2() {
-        assert_eq!(part2(&SAMPLE.into()).unwrap(), 0);
+        let sample = Input::sample(2024, 0, 1).unwrap();
+        assert_eq!(part2(&sample).unwrap(), 0);
     }
 }
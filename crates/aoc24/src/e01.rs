@@ -1,21 +1,22 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, Lines, PuzzleResult};
+use aoc::{Answer, Input, Lines, PuzzleResult, Solution};
 
-const DAY: Day = Day(1);
+pub struct HistorianHysteria;
 
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Historian Hysteria");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2024, day = 1, title = "Historian Hysteria")]
+impl Solution for HistorianHysteria {
+    type Parsed<'a> = &'a str;
 
-    let p1 = part1(input.lines()?)?;
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 2430334);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let p2 = part2(input.lines()?)?;
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 28786472);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part1((*input).into())?.into())
+    }
 
-    Ok(())
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part2((*input).into())?.into())
+    }
 }
 
 fn part1(input: Lines) -> PuzzleResult<i32> {
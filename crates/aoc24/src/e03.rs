@@ -1,22 +1,23 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, PuzzleResult};
+use aoc::{Answer, Input, PuzzleResult, Solution};
 use regex::Regex;
 
-const DAY: Day = Day(3);
+pub struct MullItOver;
 
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Mull It Over");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2024, day = 3, title = "Mull It Over")]
+impl Solution for MullItOver {
+    type Parsed<'a> = &'a str;
 
-    let p1 = part1(&input.read_to_string()?)?;
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 156388521);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let p2 = part2(&input.read_to_string()?)?;
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 75920122);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part1(input)?.into())
+    }
 
-    Ok(())
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part2(input)?.into())
+    }
 }
 
 fn part1(input: &str) -> PuzzleResult<i32> {
@@ -1,24 +1,25 @@
 use crate::e04::DiagonalDirection::{DownRight, UpRight};
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, Lines, PuzzleResult};
+use aoc::{Answer, Input, Lines, PuzzleResult, Solution};
 use std::collections::HashSet;
 use std::iter::Chain;
 
-const DAY: Day = Day(4);
+pub struct CeresSearch;
 
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Ceres Search");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2024, day = 4, title = "Ceres Search")]
+impl Solution for CeresSearch {
+    type Parsed<'a> = &'a str;
 
-    let p1 = part1(input.lines()?)?;
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 2521);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let p2 = part2(input.lines()?)?;
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 1912);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part1((*input).into())?.into())
+    }
 
-    Ok(())
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part2((*input).into())?.into())
+    }
 }
 
 fn part1(lines: Lines) -> PuzzleResult<usize> {
@@ -95,7 +96,7 @@ impl Iterator for RowsIterator<'_> {
     }
 }
 
-fn rows(matrix: &[Vec<char>]) -> RowsIterator {
+fn rows(matrix: &[Vec<char>]) -> RowsIterator<'_> {
     RowsIterator {
         matrix,
         row_index: 0,
@@ -125,7 +126,7 @@ impl Iterator for ColumnsIterator<'_> {
     }
 }
 
-fn columns(matrix: &[Vec<char>]) -> ColumnsIterator {
+fn columns(matrix: &[Vec<char>]) -> ColumnsIterator<'_> {
     ColumnsIterator {
         matrix,
         col_index: 0,
@@ -181,7 +182,7 @@ impl Iterator for DiagonalIterator<'_> {
     }
 }
 
-fn down_right_diagonals(matrix: &[Vec<char>]) -> DiagonalIterator {
+fn down_right_diagonals(matrix: &[Vec<char>]) -> DiagonalIterator<'_> {
     DiagonalIterator {
         matrix,
         offset: -((matrix.len() as i32) - 1),
@@ -190,7 +191,7 @@ fn down_right_diagonals(matrix: &[Vec<char>]) -> DiagonalIterator {
     }
 }
 
-fn up_right_diagonals(matrix: &[Vec<char>]) -> DiagonalIterator {
+fn up_right_diagonals(matrix: &[Vec<char>]) -> DiagonalIterator<'_> {
     DiagonalIterator {
         matrix,
         offset: -((matrix.len() as i32) - 1),
@@ -201,7 +202,10 @@ fn up_right_diagonals(matrix: &[Vec<char>]) -> DiagonalIterator {
 
 fn combined(
     matrix: &[Vec<char>],
-) -> Chain<Chain<Chain<RowsIterator, ColumnsIterator>, DiagonalIterator>, DiagonalIterator> {
+) -> Chain<
+    Chain<Chain<RowsIterator<'_>, ColumnsIterator<'_>>, DiagonalIterator<'_>>,
+    DiagonalIterator<'_>,
+> {
     rows(matrix)
         .chain(columns(matrix))
         .chain(down_right_diagonals(matrix))
@@ -1,23 +1,24 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, Lines, PuzzleResult};
+use aoc::{parse, Answer, PuzzleError, PuzzleResult, Solution};
 use std::cmp::Ordering;
 use std::collections::HashSet;
 
-const DAY: Day = Day(5);
+pub struct PrintQueue;
 
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Ceres Search");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2024, day = 5, title = "Print Queue")]
+impl Solution for PrintQueue {
+    type Parsed<'a> = &'a str;
 
-    let p1 = part1(input.lines()?)?;
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 5991);
+    fn parse<'a>(&self, input: &'a aoc::Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let p2 = part2(input.lines()?)?;
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 5479);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part1(input)?.into())
+    }
 
-    Ok(())
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part2(input)?.into())
+    }
 }
 
 type Page = i32;
@@ -29,8 +30,8 @@ struct Input {
     updates: Vec<Update>,
 }
 
-fn part1(lines: Lines) -> PuzzleResult<i32> {
-    let Input { ordering, updates } = parse(lines);
+fn part1(input: &str) -> PuzzleResult<i32> {
+    let Input { ordering, updates } = parse(input)?;
 
     let sum = updates
         .into_iter()
@@ -41,8 +42,8 @@ fn part1(lines: Lines) -> PuzzleResult<i32> {
     Ok(sum)
 }
 
-fn part2(lines: Lines) -> PuzzleResult<i32> {
-    let Input { ordering, updates } = parse(lines);
+fn part2(input: &str) -> PuzzleResult<i32> {
+    let Input { ordering, updates } = parse(input)?;
 
     let sum = updates
         .into_iter()
@@ -54,26 +55,31 @@ fn part2(lines: Lines) -> PuzzleResult<i32> {
     Ok(sum)
 }
 
-fn parse(lines: Lines) -> Input {
-    let lines: Vec<_> = lines.collect();
-    let sections: Vec<_> = lines.split(|line| line.is_empty()).collect();
+fn parse(input: &str) -> PuzzleResult<Input> {
+    let [ordering, updates] = parse::sections(input)[..] else {
+        return Err(PuzzleError::Input(
+            "Expected page ordering rules and updates".to_string(),
+        ));
+    };
 
-    let ordering: CompareSet = sections[0]
-        .iter()
+    let ordering = ordering
+        .lines()
         .map(|line| {
-            let (first, second) = line.split_once('|').unwrap();
-            let first: i32 = first.parse().unwrap();
-            let second: i32 = second.parse().unwrap();
-            (first, second)
+            let fields = parse::scan("{}|{}", line)?;
+            Ok((fields.get(0)?, fields.get(1)?))
         })
-        .collect();
+        .collect::<PuzzleResult<CompareSet>>()?;
 
-    let updates: Vec<_> = sections[1]
-        .iter()
-        .map(|line| line.split(',').map(|page| page.parse().unwrap()).collect())
-        .collect();
+    let updates = updates
+        .lines()
+        .map(|line| {
+            line.split(',')
+                .map(|page| parse::scan("{}", page)?.get(0))
+                .collect()
+        })
+        .collect::<PuzzleResult<Vec<Update>>>()?;
 
-    Input { updates, ordering }
+    Ok(Input { updates, ordering })
 }
 
 fn pair_compare(cmp: &CompareSet, a: &Page, b: &Page) -> Ordering {
@@ -133,11 +139,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(SAMPLE.into()).unwrap(), 143);
+        assert_eq!(part1(SAMPLE).unwrap(), 143);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(SAMPLE.into()).unwrap(), 123);
+        assert_eq!(part2(SAMPLE).unwrap(), 123);
     }
 }
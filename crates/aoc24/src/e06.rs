@@ -1,49 +1,33 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, PuzzleResult};
+use aoc::sim::Simulation;
+use aoc::viz::{Cell, Colour, Frame};
+use aoc::{
+    sim, viz, Answer, Direction, Grid, Input, Part, Point, PuzzleError, PuzzleResult, Solution,
+};
 use fxhash::FxHashSet;
-use itertools::Itertools;
 use rayon::prelude::*;
-use std::fmt::Write;
-use std::thread;
-use std::time::Duration;
 
-const DAY: Day = Day(6);
+pub struct GuardGallivant;
 
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Guard Gallivant");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2024, day = 6, title = "Guard Gallivant")]
+impl Solution for GuardGallivant {
+    type Parsed<'a> = &'a str;
 
-    let p1 = part1(&input.read_to_string()?)?;
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 4665);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let p2 = part2(&input.read_to_string()?)?;
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 1688);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part1(input)?.into())
+    }
 
-    Ok(())
-}
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part2(input)?.into())
+    }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-struct Direction(u8);
-
-const DIRECTION_NORTH_VALUE: u8 = 1;
-const DIRECTION_EAST_VALUE: u8 = 1 << 1;
-const DIRECTION_SOUTH_VALUE: u8 = 1 << 2;
-const DIRECTION_WEST_VALUE: u8 = 1 << 3;
-
-const DIRECTION_NORTH: Direction = Direction(DIRECTION_NORTH_VALUE);
-const DIRECTION_EAST: Direction = Direction(DIRECTION_EAST_VALUE);
-const DIRECTION_SOUTH: Direction = Direction(DIRECTION_SOUTH_VALUE);
-const DIRECTION_WEST: Direction = Direction(DIRECTION_WEST_VALUE);
-
-fn turn(direction: Direction) -> Direction {
-    match direction {
-        DIRECTION_NORTH => DIRECTION_EAST,
-        DIRECTION_EAST => DIRECTION_SOUTH,
-        DIRECTION_SOUTH => DIRECTION_WEST,
-        DIRECTION_WEST => DIRECTION_NORTH,
-        _ => unreachable!(),
+    /// Both parts follow the guard's patrol.
+    fn debug(&self, input: &&str, _part: Part) -> PuzzleResult<()> {
+        let (start, map) = parse(input)?;
+        sim::debug(Patrol::new(&map, start))
     }
 }
 
@@ -55,116 +39,87 @@ enum Tile {
     Obstacle,
 }
 
-const GRID_SIZE: usize = 130;
+type Map = Grid<Tile>;
+type Visited = Grid<u8>;
 
-type Visited<const N: usize> = [[u8; N]; N];
+/// How many of the last steps are drawn behind the guard.
+const TRAIL: usize = 100;
 
 fn part1(input: &str) -> PuzzleResult<usize> {
-    let (start, map, (row_count, col_count)) = parse::<GRID_SIZE>(input);
-    let mut visited = [[0; GRID_SIZE]; GRID_SIZE];
-    let ps: FxHashSet<_> = StepIterator::new(&map, &mut visited, row_count, col_count, start)
-        .map(|(p, _)| p)
-        .collect();
+    let (start, map) = parse(input)?;
+    let mut visited = Grid::new(map.width(), map.height(), 0);
+    let path: Vec<_> = StepIterator::new(&map, &mut visited, start).collect();
+    for step in 1..=path.len() {
+        viz::record(|| frame(&map, &path[step.saturating_sub(TRAIL)..step]));
+    }
+
+    let ps: FxHashSet<_> = path.iter().map(|&(p, _)| p).collect();
 
     Ok(ps.len())
 }
 
 fn part2(input: &str) -> PuzzleResult<usize> {
-    let (start, map, (row_count, col_count)) = parse::<GRID_SIZE>(input);
-    let path: Vec<_> = StepIterator::new(
-        &map,
-        &mut [[0; GRID_SIZE]; GRID_SIZE],
-        row_count,
-        col_count,
-        start,
-    )
-    .collect();
+    let (start, map) = parse(input)?;
+    let mut visited = Grid::new(map.width(), map.height(), 0);
+    let path: Vec<_> = StepIterator::new(&map, &mut visited, start).collect();
 
     let count = path
         .par_iter()
         .map(|&(pos, _)| pos)
-        .filter(|&pos| pos != start && creates_loop(&map, row_count, col_count, &path, pos))
+        .filter(|&pos| pos != start && creates_loop(&map, &path, pos))
         .collect::<FxHashSet<_>>()
         .len();
 
     Ok(count)
 }
 
-fn creates_loop<const N: usize>(
-    map: &Grid<N>,
-    row_count: usize,
-    col_count: usize,
-    path: &[((i32, i32), Direction)],
-    pos: (i32, i32),
-) -> bool {
+fn creates_loop(map: &Map, path: &[(Point, Direction)], pos: Point) -> bool {
     // Restart just before first hitting the new obstacle
     // steps > 0 since start is filtered out
-    let mut visited = [[0; N]; N];
+    let mut visited = Grid::new(map.width(), map.height(), 0);
     let step = path.iter().position(|&(p, _)| p == pos).unwrap();
-    for ((r, c), d) in &path[..step - 1] {
-        visited[*r as usize][*c as usize] |= d.0;
+    for (p, d) in &path[..step - 1] {
+        visited[*p] |= d.mask();
     }
 
     let (start, dir) = path[step - 1];
-    let mut it = StepIterator::from_state(
-        map,
-        &mut visited,
-        row_count,
-        col_count,
-        start,
-        dir,
-        Some((pos.0, pos.1)),
-    );
+    let mut it = StepIterator::from_state(map, &mut visited, start, dir, Some(pos));
     // Drain the iterator
     it.by_ref().for_each(drop);
     it.is_valid()
 }
 
-struct StepIterator<'a, const N: usize> {
-    map: &'a Grid<N>,
-    visited: &'a mut Visited<N>,
-    row_count: usize,
-    col_count: usize,
-    pos: (i32, i32),
+struct StepIterator<'a> {
+    map: &'a Map,
+    visited: &'a mut Visited,
+    pos: Point,
     dir: Direction,
     first: bool,
-    extra_obstacle: Option<(i32, i32)>,
+    extra_obstacle: Option<Point>,
 }
 
-impl<'a, const N: usize> StepIterator<'a, N> {
-    fn new(
-        map: &'a Grid<N>,
-        visited: &'a mut Visited<N>,
-        row_count: usize,
-        col_count: usize,
-        pos: (i32, i32),
-    ) -> Self {
+impl<'a> StepIterator<'a> {
+    fn new(map: &'a Map, visited: &'a mut Visited, pos: Point) -> Self {
         Self {
             map,
             visited,
-            row_count,
-            col_count,
             pos,
-            dir: DIRECTION_NORTH,
+            dir: Direction::North,
             first: true,
             extra_obstacle: None,
         }
     }
 
     fn from_state(
-        map: &'a Grid<N>,
-        visited: &'a mut Visited<N>,
-        row_count: usize,
-        col_count: usize,
-        pos: (i32, i32),
+        map: &'a Map,
+        visited: &'a mut Visited,
+        pos: Point,
         dir: Direction,
-        extra_obstacle: Option<(i32, i32)>,
+        extra_obstacle: Option<Point>,
     ) -> Self {
         Self {
             map,
             visited,
-            row_count,
-            col_count,
             pos,
             dir,
             first: false,
@@ -172,43 +127,13 @@ impl<'a, const N: usize> StepIterator<'a, N> {
         }
     }
 
-    fn valid(&self, r: i32, c: i32) -> bool {
-        0 <= r && r < self.row_count as i32 && 0 <= c && c < self.col_count as i32
-    }
-
     fn is_valid(&self) -> bool {
-        self.valid(self.pos.0, self.pos.1)
-    }
-
-    #[allow(dead_code)]
-    fn animate(&mut self) {
-        let ps: Vec<_> = self.collect();
-
-        print_map(self.map, self.row_count, self.col_count);
-
-        let mut marks = Vec::<((i32, i32), Direction)>::new();
-        for (count, ((r, c), d)) in ps.into_iter().enumerate() {
-            marks.push(((r, c), d));
-            while marks.len() > 100 {
-                marks.remove(0);
-            }
-
-            let mut visited = [[0; N]; N];
-            for ((r, c), d) in &marks {
-                visited[*r as usize][*c as usize] = d.0;
-            }
-
-            if count % 2 == 0 {
-                print!("\x1B[2J\x1B[1;1H");
-                print_map_with_history(self.map, &visited, self.row_count, self.col_count);
-                thread::sleep(Duration::from_millis(20));
-            }
-        }
+        self.map.at(self.pos).is_some()
     }
 }
 
-impl<const N: usize> Iterator for StepIterator<'_, N> {
-    type Item = ((i32, i32), Direction);
+impl Iterator for StepIterator<'_> {
+    type Item = (Point, Direction);
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.first {
@@ -216,149 +141,128 @@ impl<const N: usize> Iterator for StepIterator<'_, N> {
             return Some((self.pos, self.dir));
         }
 
-        let v = &mut self.visited[self.pos.0 as usize][self.pos.1 as usize];
-        if (*v & self.dir.0) != 0 {
+        let v = &mut self.visited[self.pos];
+        if (*v & self.dir.mask()) != 0 {
             return None;
         } else {
-            *v |= self.dir.0;
+            *v |= self.dir.mask();
         }
 
-        let (r, c) = self.pos;
-        if !self.valid(r, c) {
-            return None;
+        let next = self.pos + self.dir;
+        match self.map.at(next) {
+            None => {
+                self.pos = next;
+                return None;
+            }
+            Some(&tile) if tile == Tile::Obstacle || self.extra_obstacle == Some(next) => {
+                self.dir = self.dir.turn_right();
+                return self.next();
+            }
+            Some(_) => {}
         }
 
-        let (next_r, next_c) = match self.dir {
-            DIRECTION_NORTH => (r - 1, c),
-            DIRECTION_EAST => (r, c + 1),
-            DIRECTION_SOUTH => (r + 1, c),
-            DIRECTION_WEST => (r, c - 1),
-            _ => unreachable!(),
-        };
+        self.pos = next;
+        Some((self.pos, self.dir))
+    }
+}
 
-        if !self.valid(next_r, next_c) {
-            self.pos = (next_r, next_c);
-            return None;
-        }
+/// The guard's walk one step at a time, as a [`Simulation`].
+#[derive(Clone)]
+struct Patrol<'a> {
+    map: &'a Map,
+    visited: Visited,
+    pos: Point,
+    dir: Direction,
+}
 
-        if self.map[next_r as usize][next_c as usize] == Tile::Obstacle
-            || (self.extra_obstacle == Some((next_r, next_c)))
-        {
-            self.dir = turn(self.dir);
-            return self.next();
+impl<'a> Patrol<'a> {
+    fn new(map: &'a Map, start: Point) -> Self {
+        Self {
+            map,
+            visited: Grid::new(map.width(), map.height(), 0),
+            pos: start,
+            dir: Direction::North,
         }
-
-        self.pos = (next_r, next_c);
-        Some((self.pos, self.dir))
     }
 }
 
-type Grid<const N: usize> = [[Tile; N]; N];
-
-fn parse<const N: usize>(input: &str) -> ((i32, i32), Grid<N>, (usize, usize)) {
-    let mut start: Option<(i32, i32)> = None;
-
-    let map: Vec<Vec<Tile>> = input
-        .lines()
-        .enumerate()
-        .map(|(r, l)| {
-            l.chars()
-                .enumerate()
-                .map(|(c, ch)| match ch {
-                    '.' => Tile::Open,
-                    '^' => {
-                        assert_eq!(start, None);
-                        start = Some((r as i32, c as i32));
-                        Tile::Start
-                    }
-                    '#' => Tile::Obstacle,
-                    _ => Tile::OutOfBounds,
-                })
-                .collect()
-        })
-        .collect();
-
-    let mut grid = [[Tile::OutOfBounds; N]; N];
-    for (r, row) in map.iter().enumerate() {
-        for (c, &tile) in row.iter().enumerate() {
-            grid[r][c] = tile;
+impl Simulation for Patrol<'_> {
+    /// Leaves the guard where it is on the step that would leave the map,
+    /// so the end looks the same whether it is stepped to or replayed.
+    fn step(&mut self) -> bool {
+        let mask = self.visited[self.pos];
+        let mut it =
+            StepIterator::from_state(self.map, &mut self.visited, self.pos, self.dir, None);
+        let Some((pos, dir)) = it.next() else {
+            self.visited[self.pos] = mask;
+            return false;
+        };
+        (self.pos, self.dir) = (pos, dir);
+        true
+    }
+
+    fn frame(&self) -> Frame {
+        let mut frame = frame(self.map, &[(self.pos, self.dir)]);
+        for (pos, &mask) in self.visited.iter().filter(|(_, &mask)| mask != 0) {
+            let arrow = Direction::from_mask(mask).map_or('+', Direction::arrow);
+            frame.set(pos, Cell::coloured(arrow, Colour::Yellow));
         }
+        if let Some(pos) = self.pos.pos() {
+            frame.set(pos, Cell::coloured('@', Colour::Red));
+        }
+        frame
     }
 
-    (start.unwrap(), grid, (map.len(), map[0].len()))
+    fn inspect(&self) -> String {
+        let visited = self
+            .visited
+            .iter()
+            .filter(|&(pos, &mask)| mask != 0 || Point::from(pos) == self.pos)
+            .count();
+        format!(
+            "guard at {} facing {:?}, {} tiles visited",
+            self.pos, self.dir, visited
+        )
+    }
 }
 
-#[allow(dead_code)]
-fn print_map<const N: usize>(map: &Grid<N>, row_count: usize, col_count: usize) {
-    println!(
-        "{}",
-        map.iter()
-            .take(row_count)
-            .map(|r| r
-                .iter()
-                .take(col_count)
-                .map(|&c| match c {
-                    Tile::OutOfBounds => ' ',
-                    Tile::Open => '.',
-                    Tile::Start => '^',
-                    Tile::Obstacle => '#',
-                })
-                .fold(String::new(), |mut acc, c| {
-                    write!(acc, "{} ", c).unwrap();
-                    acc
-                }))
-            .join("\n")
-    );
+fn parse(input: &str) -> PuzzleResult<(Point, Map)> {
+    let map = Grid::parse(input, |ch| match ch {
+        '.' => Tile::Open,
+        '^' => Tile::Start,
+        '#' => Tile::Obstacle,
+        _ => Tile::OutOfBounds,
+    })?;
+    let start = map
+        .find(|&tile| tile == Tile::Start)
+        .ok_or_else(|| PuzzleError::Input("No start in the map".to_string()))?;
+
+    Ok((start.into(), map))
 }
 
-#[allow(dead_code)]
-fn print_map_with_history<const N: usize>(
-    map: &Grid<N>,
-    visited: &Visited<N>,
-    row_count: usize,
-    col_count: usize,
-) {
-    println!(
-        "{}",
-        map.iter()
-            .zip(visited.iter())
-            .take(row_count)
-            .map(|(r, v)| r
-                .iter()
-                .zip(v.iter())
-                .take(col_count)
-                .map(|(m, v)| match m {
-                    Tile::OutOfBounds => ' ',
-                    Tile::Open => {
-                        match *v {
-                            0 => ' ',
-                            DIRECTION_NORTH_VALUE => '^',
-                            DIRECTION_EAST_VALUE => '>',
-                            DIRECTION_SOUTH_VALUE => 'v',
-                            DIRECTION_WEST_VALUE => '<',
-                            _ => 'X',
-                        }
-                    }
-                    Tile::Start => {
-                        if *v == 0 {
-                            '^'
-                        } else {
-                            'X'
-                        }
-                    }
-                    Tile::Obstacle => '#',
-                })
-                .fold(String::new(), |mut acc, c| {
-                    write!(acc, "{} ", c).unwrap();
-                    acc
-                }))
-            .join("\n")
-    );
+/// The map with the guard at the end of the trail of steps behind it.
+fn frame(map: &Map, trail: &[(Point, Direction)]) -> Frame {
+    let mut frame = Frame::from_grid(map, |tile| match tile {
+        Tile::OutOfBounds => Cell::plain(' '),
+        Tile::Open => Cell::plain('.'),
+        Tile::Start => Cell::coloured('^', Colour::Green),
+        Tile::Obstacle => Cell::coloured('#', Colour::Grey),
+    });
+    for &(p, d) in trail {
+        if let Some(pos) = p.pos() {
+            frame.set(pos, Cell::coloured(d.arrow(), Colour::Yellow));
+        }
+    }
+    if let Some(pos) = trail.last().and_then(|(p, _)| p.pos()) {
+        frame.set(pos, Cell::coloured('@', Colour::Red));
+    }
+    frame
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc::sim::Debugger;
     use std::collections::HashSet;
 
     const SAMPLE: &str = "\
@@ -376,27 +280,57 @@ mod tests {
 
     #[test]
     fn test_parse() {
-        let (start, _map, (row_count, col_count)) = parse::<16>(SAMPLE);
-        assert_eq!(start, (6, 4));
-        assert_eq!(row_count, 10);
-        assert_eq!(col_count, 10);
+        let (start, map) = parse(SAMPLE).unwrap();
+        assert_eq!(start, Point::new(4, 6));
+        assert_eq!(map.height(), 10);
+        assert_eq!(map.width(), 10);
     }
 
     #[test]
     fn test_iterator() {
-        let (start, map, (row_count, col_count)) = parse::<16>(SAMPLE);
-        let mut visited = [[0; 16]; 16];
-        let ps: HashSet<_> = StepIterator::new(&map, &mut visited, row_count, col_count, start)
+        let (start, map) = parse(SAMPLE).unwrap();
+        let mut visited = Grid::new(map.width(), map.height(), 0);
+        let ps: HashSet<_> = StepIterator::new(&map, &mut visited, start)
             .map(|(p, _)| p)
             .collect();
         assert_eq!(ps.len(), 41);
     }
 
     #[test]
-    #[ignore] // Only test manually
-    fn test_print() {
-        let (_, map, (row_count, col_count)) = parse::<16>(SAMPLE);
-        print_map(&map, row_count, col_count);
+    fn test_frame() {
+        let (start, map) = parse(SAMPLE).unwrap();
+        let text = frame(&map, &[]).to_string();
+        assert_eq!(text.lines().nth(6), Some(".#..^....."));
+
+        let trail = [
+            (start, Direction::North),
+            (start + Direction::North, Direction::North),
+        ];
+        let text = frame(&map, &trail).to_string();
+        assert_eq!(text.lines().nth(5), Some("....@....."));
+    }
+
+    #[test]
+    fn test_patrol() {
+        let (start, map) = parse(SAMPLE).unwrap();
+        let mut debugger = Debugger::new(Patrol::new(&map, start));
+        assert!(!debugger.forward(100));
+        assert_eq!(debugger.step(), 44);
+        assert!(debugger.state().inspect().ends_with("41 tiles visited"));
+
+        let end = (
+            debugger.state().pos,
+            debugger.state().dir,
+            debugger.state().inspect(),
+        );
+        assert_eq!(end.0, Point::new(7, 9));
+
+        assert!(debugger.goto(1));
+        assert_eq!(debugger.state().pos, Point::new(4, 5));
+
+        assert!(debugger.goto(44));
+        let state = debugger.state();
+        assert_eq!((state.pos, state.dir, state.inspect()), end);
     }
 
     #[test]
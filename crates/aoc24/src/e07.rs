@@ -1,23 +1,24 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, Input, InputFetcher, Lines, PuzzleError, PuzzleResult};
+use aoc::{Answer, Input, Lines, PuzzleError, PuzzleResult, Solution};
 use itertools::Itertools;
 use rayon::prelude::*;
 
-const DAY: Day = Day(7);
+pub struct BridgeRepair;
 
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Bridge Repair");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2024, day = 7, title = "Bridge Repair")]
+impl Solution for BridgeRepair {
+    type Parsed<'a> = &'a str;
 
-    let p1 = part1(&input)?;
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 7579994664753);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let p2 = part2(&input)?;
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 438027111276610);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part1(&(*input).into())?.into())
+    }
 
-    Ok(())
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part2(&(*input).into())?.into())
+    }
 }
 
 type Value = i64;
@@ -1,35 +1,36 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, Input, InputFetcher, PuzzleError, PuzzleResult};
+use aoc::{Answer, Input, PuzzleError, PuzzleResult, Solution};
 use fxhash::FxHashMap;
 use std::fmt;
 
-const DAY: Day = Day(8);
+pub struct ResonantCollinearity;
 
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Resonant Collinearity");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2024, day = 8, title = "Resonant Collinearity")]
+impl Solution for ResonantCollinearity {
+    type Parsed<'a> = &'a str;
 
-    let p1 = part1(&input)?;
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 301);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let p2 = part2(&input)?;
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 1019);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part1(&(*input).into())?.into())
+    }
 
-    Ok(())
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part2(&(*input).into())?.into())
+    }
 }
 
 fn part1(input: &Input) -> PuzzleResult<usize> {
-    let input = input.read_to_string()?;
-    let mut map = Map::from(&input)?;
+    let input = input.text()?;
+    let mut map = Map::from(input)?;
     map.locate_first_antinodes();
     Ok(map.count_antinodes())
 }
 
 fn part2(input: &Input) -> PuzzleResult<usize> {
-    let input = input.read_to_string()?;
-    let mut map = Map::from(&input)?;
+    let input = input.text()?;
+    let mut map = Map::from(input)?;
     map.locate_all_antinodes();
     Ok(map.count_antinodes())
 }
@@ -1,32 +1,33 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, Input, InputFetcher, PuzzleResult};
+use aoc::{Answer, Input, PuzzleResult, Solution};
 use std::fmt::Display;
 
-const DAY: Day = Day(9);
+pub struct DiskFragmenter;
 
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Disk Fragmenter");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2024, day = 9, title = "Disk Fragmenter")]
+impl Solution for DiskFragmenter {
+    type Parsed<'a> = &'a str;
 
-    let p1 = part1(&input)?;
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 6367087064415);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let p2 = part2(&input)?;
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 6390781891880);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part1(&(*input).into())?.into())
+    }
 
-    Ok(())
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part2(&(*input).into())?.into())
+    }
 }
 
 fn part1(input: &Input) -> PuzzleResult<usize> {
-    let mut d = Disk::from_str(&input.read_to_string()?);
+    let mut d = Disk::from_str(input.text()?);
     d.compact();
     Ok(d.checksum())
 }
 
 fn part2(input: &Input) -> PuzzleResult<usize> {
-    let d = Disk::from_str(&input.read_to_string()?);
+    let d = Disk::from_str(input.text()?);
     d.compact_checksum()
 }
 
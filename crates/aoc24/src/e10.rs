@@ -1,86 +1,75 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, InputFetcher, PuzzleError, PuzzleResult};
+use aoc::grid::{Grid, Pos};
+use aoc::{Answer, Input, PuzzleError, PuzzleResult, Solution};
 use itertools::Itertools;
 
-const DAY: Day = Day(10);
+pub struct HoofIt;
 
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Hoof It");
-    let input = aoc.get_input(YEAR, DAY)?.read_to_string()?;
-    let (p2, p1) = parts2and1(&input)?;
+#[aoc::solution(year = 2024, day = 10, title = "Hoof It")]
+impl Solution for HoofIt {
+    type Parsed<'a> = Vec<Vec<Pos>>;
 
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 644);
+    fn parse(&self, input: &Input) -> PuzzleResult<Vec<Vec<Pos>>> {
+        let grid = parse(input.text()?)?;
+        Ok(find_heads(&grid)
+            .into_iter()
+            .map(|h| find_peaks(h, &grid))
+            .collect())
+    }
 
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 1366);
+    fn part1(&self, trails: &Vec<Vec<Pos>>) -> PuzzleResult<Answer> {
+        Ok(part1(trails).into())
+    }
 
-    Ok(())
+    fn part2(&self, trails: &Vec<Vec<Pos>>) -> PuzzleResult<Answer> {
+        Ok(part2(trails).into())
+    }
 }
 
-fn parts2and1(input: &str) -> PuzzleResult<(usize, usize)> {
-    let mut grid = [[-1; 64]; 64];
-    parse(input, &mut grid)?;
-    let scores = find_heads(&grid)
-        .into_iter()
-        .map(|h| find_peak(h, &grid))
-        .fold((0, 0), |(acc1, acc2), (val1, val2)| {
-            (acc1 + val1, acc2 + val2)
-        });
-
-    Ok(scores)
+/// The sum of the number of peaks each trailhead reaches.
+fn part1(trails: &[Vec<Pos>]) -> usize {
+    trails
+        .iter()
+        .map(|peaks| peaks.iter().unique().count())
+        .sum()
 }
 
-fn parse<const N: usize>(input: &str, grid: &mut [[i8; N]; N]) -> PuzzleResult<()> {
-    for (i, line) in input.lines().enumerate() {
-        for (j, c) in line.chars().enumerate() {
-            grid[1 + i][1 + j] = c
-                .to_digit(10)
-                .unwrap()
-                .try_into()
-                .map_err(|_| PuzzleError::Input(format!("Not a digit: {c}")))?;
-        }
-    }
+/// The sum of the number of trails from each trailhead.
+fn part2(trails: &[Vec<Pos>]) -> usize {
+    trails.iter().map(|peaks| peaks.len()).sum()
+}
 
-    Ok(())
+fn parse(input: &str) -> PuzzleResult<Grid<u8>> {
+    Grid::try_parse(input, |c| {
+        c.to_digit(10)
+            .map(|d| d as u8)
+            .ok_or_else(|| PuzzleError::Input(format!("Not a digit: {c}")))
+    })
 }
 
-fn find_heads<const N: usize>(grid: &[[i8; N]; N]) -> Vec<(usize, usize)> {
+fn find_heads(grid: &Grid<u8>) -> Vec<Pos> {
     grid.iter()
-        .enumerate()
-        .flat_map(|(r, row)| {
-            row.iter()
-                .enumerate()
-                .filter(|(_, h)| **h == 0)
-                .map(move |(c, _)| (r, c))
-        })
+        .filter(|(_, &h)| h == 0)
+        .map(|(pos, _)| pos)
         .collect()
 }
 
-fn find_peak<const N: usize>(head: (usize, usize), grid: &[[i8; N]; N]) -> (usize, usize) {
+/// The peak at the end of every trail from `head`, so once per trail.
+fn find_peaks(head: Pos, grid: &Grid<u8>) -> Vec<Pos> {
     let mut peaks = Vec::new();
     let mut queue = vec![head];
     while let Some(pos) = queue.pop() {
-        let height = grid[pos.0][pos.1];
+        let height = grid[pos];
         if height == 9 {
             peaks.push(pos);
             continue;
         }
 
-        let moves = [
-            (pos.0 - 1, pos.1),
-            (pos.0 + 1, pos.1),
-            (pos.0, pos.1 - 1),
-            (pos.0, pos.1 + 1),
-        ];
-
-        moves
-            .into_iter()
-            .filter(|m| grid[m.0][m.1] == height + 1)
+        grid.neighbours4(pos)
+            .filter(|&m| grid[m] == height + 1)
             .for_each(|m| queue.push(m));
     }
 
-    (peaks.len(), peaks.into_iter().unique().count())
+    peaks
 }
 
 #[cfg(test)]
@@ -100,13 +89,13 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let (_, p1) = parts2and1(SAMPLE).unwrap();
-        assert_eq!(p1, 36);
+        let trails = HoofIt.parse(&SAMPLE.into()).unwrap();
+        assert_eq!(part1(&trails), 36);
     }
 
     #[test]
     fn test_part2() {
-        let (p2, _) = parts2and1(SAMPLE).unwrap();
-        assert_eq!(p2, 81);
+        let trails = HoofIt.parse(&SAMPLE.into()).unwrap();
+        assert_eq!(part2(&trails), 81);
     }
 }
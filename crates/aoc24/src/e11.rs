@@ -1,22 +1,23 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, Input, InputFetcher, PuzzleError, PuzzleResult};
+use aoc::{Answer, Input, PuzzleError, PuzzleResult, Solution};
 use fxhash::FxHashMap;
 
-const DAY: Day = Day(11);
+pub struct PlutonianPebbles;
 
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Plutonian Pebbles");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2024, day = 11, title = "Plutonian Pebbles")]
+impl Solution for PlutonianPebbles {
+    type Parsed<'a> = &'a str;
 
-    let p1 = part1(&input)?;
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 182081);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let p2 = part2(&input)?;
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 216318908621637);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part1(&(*input).into())?.into())
+    }
 
-    Ok(())
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part2(&(*input).into())?.into())
+    }
 }
 
 fn part1(input: &Input) -> PuzzleResult<usize> {
@@ -39,7 +40,7 @@ fn blink(mut stones: FxHashMap<Value, Value>, n: usize) -> Result<usize, PuzzleE
 
 fn parse(input: &Input) -> PuzzleResult<FxHashMap<Value, Value>> {
     let histogram = input
-        .read_to_string()?
+        .text()?
         .split_whitespace()
         .map(|w| w.parse::<Value>().unwrap())
         .fold(FxHashMap::default(), |mut acc, x| {
@@ -64,7 +65,7 @@ fn step(input: &FxHashMap<Value, Value>) -> FxHashMap<Value, Value> {
         }
 
         let len = ((k as f64).log10() as usize) + 1;
-        if len % 2 == 0 {
+        if len.is_multiple_of(2) {
             let divisor = (10 as Value).pow((len / 2) as u32);
             increase(&mut result, k / divisor, v);
             increase(&mut result, k % divisor, v);
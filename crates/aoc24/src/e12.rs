@@ -1,30 +1,30 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, Input, InputFetcher, PuzzleResult};
+use aoc::grid::{Grid, Pos, OFFSETS_4};
+use aoc::{Answer, Input, PuzzleResult, Solution};
 use fxhash::FxHashMap;
 use std::collections::VecDeque;
 
-const DAY: Day = Day(12);
+pub struct GardenGroups;
 
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Garden Groups");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2024, day = 12, title = "Garden Groups")]
+impl Solution for GardenGroups {
+    type Parsed<'a> = &'a str;
 
-    let p1 = part1(&input)?;
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 1477924);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let p2 = part2(&input)?;
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 841934);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part1(&(*input).into())?.into())
+    }
 
-    Ok(())
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part2(&(*input).into())?.into())
+    }
 }
 
-const N: usize = 140;
-
 #[derive(Clone, Debug)]
 struct Map {
-    grid: [[(char, u16); N]; N],
+    grid: Grid<(char, u16)>,
     width: usize,
     height: usize,
 }
@@ -37,24 +37,14 @@ enum Side {
 }
 
 impl Map {
-    fn parse(input: &str) -> Self {
-        let mut grid = [[(' ', 0); N]; N];
-        let mut width = 0;
-        let mut height = 0;
-
-        input.lines().enumerate().for_each(|(r, line)| {
-            width = width.max(line.len());
-            height = height.max(r + 1);
-            line.chars().enumerate().for_each(|(c, ch)| {
-                grid[r][c].0 = ch;
-            });
-        });
-
-        Self {
+    fn parse(input: &str) -> PuzzleResult<Self> {
+        let grid = Grid::parse(input, |ch| (ch, 0))?;
+
+        Ok(Self {
+            width: grid.width(),
+            height: grid.height(),
             grid,
-            width,
-            height,
-        }
+        })
     }
 
     fn identify_regions(&mut self) -> FxHashMap<u16, (u16, u16)> {
@@ -63,49 +53,33 @@ impl Map {
 
         for r in 0..self.height {
             for c in 0..self.width {
-                if self.grid[r][c].1 == 0 {
+                if self.grid[(r, c)].1 == 0 {
                     let id = next_id;
                     next_id += 1;
 
-                    let ch = self.grid[r][c].0;
+                    let ch = self.grid[(r, c)].0;
                     let mut area = 0u16;
                     let mut perimeter = 0u16;
-                    let mut queue = VecDeque::<(usize, usize)>::new();
-                    self.grid[r][c].1 = id;
+                    let mut queue = VecDeque::<Pos>::new();
+                    self.grid[(r, c)].1 = id;
                     queue.push_back((r, c));
 
-                    while let Some((r, c)) = queue.pop_back() {
-                        assert_ne!(self.grid[r][c].1, 0);
+                    while let Some(pos) = queue.pop_back() {
+                        assert_ne!(self.grid[pos].1, 0);
 
                         area += 1;
                         perimeter += 4;
 
-                        if r > 0 && self.grid[r - 1][c].0 == ch {
-                            perimeter -= 1;
-                            if self.grid[r - 1][c].1 == 0 {
-                                self.grid[r - 1][c].1 = id;
-                                queue.push_back((r - 1, c));
-                            }
-                        }
-                        if r < self.height - 1 && self.grid[r + 1][c].0 == ch {
-                            perimeter -= 1;
-                            if self.grid[r + 1][c].1 == 0 {
-                                self.grid[r + 1][c].1 = id;
-                                queue.push_back((r + 1, c));
-                            }
-                        }
-                        if c > 0 && self.grid[r][c - 1].0 == ch {
-                            perimeter -= 1;
-                            if self.grid[r][c - 1].1 == 0 {
-                                self.grid[r][c - 1].1 = id;
-                                queue.push_back((r, c - 1));
-                            }
-                        }
-                        if c < self.width - 1 && self.grid[r][c + 1].0 == ch {
-                            perimeter -= 1;
-                            if self.grid[r][c + 1].1 == 0 {
-                                self.grid[r][c + 1].1 = id;
-                                queue.push_back((r, c + 1));
+                        for offset in OFFSETS_4 {
+                            let Some(next) = self.grid.offset(pos, offset) else {
+                                continue;
+                            };
+                            if self.grid[next].0 == ch {
+                                perimeter -= 1;
+                                if self.grid[next].1 == 0 {
+                                    self.grid[next].1 = id;
+                                    queue.push_back(next);
+                                }
                             }
                         }
                     }
@@ -153,28 +127,28 @@ impl Map {
         // Top line
         let mut side = Side::None;
         for c in 0..self.width {
-            let cell = self.grid[0][c].1;
+            let cell = self.grid[(0, c)].1;
             count += handle_edge(cell, &mut side);
         }
 
         // Bottom line
         let mut side = Side::None;
         for c in 0..self.width {
-            let cell = self.grid[self.height - 1][c].1;
+            let cell = self.grid[(self.height - 1, c)].1;
             count += handle_edge(cell, &mut side);
         }
 
         // Left side
         let mut side = Side::None;
         for r in 0..self.height {
-            let cell = self.grid[r][0].1;
+            let cell = self.grid[(r, 0)].1;
             count += handle_edge(cell, &mut side);
         }
 
         // Right side
         let mut side = Side::None;
         for r in 0..self.height {
-            let cell = self.grid[r][self.width - 1].1;
+            let cell = self.grid[(r, self.width - 1)].1;
             count += handle_edge(cell, &mut side);
         }
 
@@ -182,8 +156,8 @@ impl Map {
         for r in 0..self.height - 1 {
             let mut side = Side::None;
             for c in 0..self.width {
-                let upper = self.grid[r][c].1;
-                let lower = self.grid[r + 1][c].1;
+                let upper = self.grid[(r, c)].1;
+                let lower = self.grid[(r + 1, c)].1;
                 count += handle_mid(upper, lower, &mut side);
             }
         }
@@ -192,8 +166,8 @@ impl Map {
         for c in 0..self.width - 1 {
             let mut side = Side::None;
             for r in 0..self.height {
-                let left = self.grid[r][c].1;
-                let right = self.grid[r][c + 1].1;
+                let left = self.grid[(r, c)].1;
+                let right = self.grid[(r, c + 1)].1;
                 count += handle_mid(left, right, &mut side);
             }
         }
@@ -203,7 +177,7 @@ impl Map {
 }
 
 fn part1(input: &Input) -> PuzzleResult<i32> {
-    let mut map = Map::parse(&input.read_to_string()?);
+    let mut map = Map::parse(input.text()?)?;
     let regions = map.identify_regions();
     let cost = regions
         .values()
@@ -213,7 +187,7 @@ fn part1(input: &Input) -> PuzzleResult<i32> {
 }
 
 fn part2(input: &Input) -> PuzzleResult<usize> {
-    let mut map = Map::parse(&input.read_to_string()?);
+    let mut map = Map::parse(input.text()?)?;
     let cost = map
         .identify_regions()
         .iter()
@@ -1,22 +1,22 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, Input, InputFetcher, PuzzleResult};
-use itertools::Itertools;
+use aoc::{parse, Answer, Input, PuzzleResult, Solution};
 
-const DAY: Day = Day(13);
+pub struct ClawContraption;
 
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Claw Contraption");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2024, day = 13, title = "Claw Contraption")]
+impl Solution for ClawContraption {
+    type Parsed<'a> = &'a str;
 
-    let p1 = part1(&input)?;
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 28262);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let p2 = part2(&input)?;
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 101406661266314);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part1(&(*input).into())?.into())
+    }
 
-    Ok(())
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part2(&(*input).into())?.into())
+    }
 }
 
 fn part1(input: &Input) -> PuzzleResult<i64> {
@@ -43,51 +43,30 @@ fn part2(input: &Input) -> PuzzleResult<i64> {
     Ok(cost)
 }
 
-fn parse(input: &Input) -> PuzzleResult<Vec<ClawContraption>> {
-    Ok(input
-        .read_to_string()?
-        .lines()
-        .chunks(4)
+fn parse(input: &Input) -> PuzzleResult<Vec<ClawMachine>> {
+    input
+        .sections()?
         .into_iter()
-        .map(|chunk| {
-            ClawContraption::parse(
-                &chunk
-                    .filter(|s| !s.is_empty())
-                    .map(|s| s.to_string())
-                    .collect::<Vec<_>>(),
-            )
-        })
-        .collect::<Vec<_>>())
+        .map(ClawMachine::parse)
+        .collect()
 }
 
 #[derive(Debug)]
-struct ClawContraption {
+struct ClawMachine {
     a_d: (i64, i64),
     b_d: (i64, i64),
     prize: (i64, i64),
 }
 
-impl ClawContraption {
-    fn parse(lines: &[String]) -> Self {
-        assert_eq!(lines.len(), 3);
+impl ClawMachine {
+    fn parse(section: &str) -> PuzzleResult<Self> {
+        let [a_x, a_y, b_x, b_y, prize_x, prize_y] = parse::ints_n(section)?;
 
-        fn parse_coordinates(line: &str, x_prefix: &str, y_prefix: &str) -> (i64, i64) {
-            let (_, coords) = line.split_once(": ").unwrap();
-            let (x_str, y_str) = coords.split_once(", ").unwrap();
-            let x = x_str.trim_start_matches(x_prefix).parse().unwrap();
-            let y = y_str.trim_start_matches(y_prefix).parse().unwrap();
-            (x, y)
-        }
-
-        let (a_x, a_y) = parse_coordinates(&lines[0], "X+", "Y+");
-        let (b_x, b_y) = parse_coordinates(&lines[1], "X+", "Y+");
-        let (price_x, price_y) = parse_coordinates(&lines[2], "X=", "Y=");
-
-        Self {
+        Ok(Self {
             a_d: (a_x, a_y),
             b_d: (b_x, b_y),
-            prize: (price_x, price_y),
-        }
+            prize: (prize_x, prize_y),
+        })
     }
 
     fn solve(&self) -> Option<i64> {
@@ -140,12 +119,7 @@ Prize: X=18641, Y=10279
 
     #[test]
     fn test_parse_entity() {
-        let input = ClawContraption::parse(
-            &SAMPLE_ENTITY
-                .lines()
-                .map(|s| s.to_string())
-                .collect::<Vec<_>>(),
-        );
+        let input = ClawMachine::parse(SAMPLE_ENTITY).unwrap();
 
         assert_eq!(input.a_d, (94, 34));
         assert_eq!(input.b_d, (22, 67));
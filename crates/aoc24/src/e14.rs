@@ -1,27 +1,36 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, Input, InputFetcher, PuzzleResult};
+use aoc::sim::Simulation;
+use aoc::viz::{Cell, Colour, Frame};
+use aoc::{parse, sim, viz, Answer, Input, Part, PuzzleResult, Solution};
 
-const DAY: Day = Day(14);
+pub struct RestroomRedoubt;
 
-#[allow(dead_code)]
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Restroom Redoubt");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2024, day = 14, title = "Restroom Redoubt")]
+impl Solution for RestroomRedoubt {
+    type Parsed<'a> = &'a str;
 
-    let p1 = part1(&input, 101, 103)?;
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 225943500);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
+
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part1(&(*input).into(), 101, 103)?.into())
+    }
 
-    let p2 = part2(&input, 101, 103)?;
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 6377);
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part2(&(*input).into(), 101, 103)?.into())
+    }
 
-    Ok(())
+    /// Both parts move the same robots. `u blob Both` runs until the tree.
+    fn debug(&self, input: &&str, _part: Part) -> PuzzleResult<()> {
+        sim::debug(Scene::parse(101, 103, &(*input).into())?)
+    }
 }
 
 fn part1(input: &Input, width: usize, height: usize) -> PuzzleResult<usize> {
     let mut scene = Scene::parse(width, height, input)?;
+    viz::record(|| scene.frame());
     scene.step_n(100);
+    viz::record(|| scene.frame());
     Ok(scene.safety_factor())
 }
 
@@ -36,6 +45,7 @@ fn part2(input: &Input, width: usize, height: usize) -> PuzzleResult<usize> {
 
 fn is_xmas_tree(mut scene: Scene, seconds: usize) -> bool {
     scene.step_n(seconds);
+    viz::record(|| scene.frame());
     scene.detect_blob() == BlobDimension::Both
 }
 
@@ -63,7 +73,11 @@ fn find_periods(mut scene: Scene) -> (usize, usize, usize, usize) {
     let mut horizontals = Vec::new();
 
     while verticals.len() < 2 || horizontals.len() < 2 {
-        match scene.detect_blob() {
+        let blob = scene.detect_blob();
+        if blob != BlobDimension::None {
+            viz::record(|| scene.frame());
+        }
+        match blob {
             BlobDimension::Vertical => verticals.push(scene.seconds),
             BlobDimension::Horizontal => horizontals.push(scene.seconds),
             BlobDimension::Both => {
@@ -131,22 +145,10 @@ impl Scene {
     }
 
     fn parse(width: usize, height: usize, input: &Input) -> PuzzleResult<Self> {
-        let line_re = regex::Regex::new(r"p=(\d+),(\d+) v=(-?\d+),(-?\d+)").unwrap();
-
-        let robots = input
-            .lines()?
-            .map(|line| {
-                let caps = line_re.captures(&line).unwrap();
-                let values: Vec<Value> = caps
-                    .iter()
-                    .skip(1)
-                    .flatten()
-                    .map(|v| v.as_str().parse().unwrap())
-                    .collect();
-
-                Robot::new((values[0], values[1]), (values[2], values[3]))
-            })
-            .collect::<Vec<_>>();
+        let robots = parse::lines(input.text()?, |line| {
+            let [x, y, dx, dy] = parse::ints_n(line)?;
+            Ok(Robot::new((x, y), (dx, dy)))
+        })?;
 
         Ok(Self::new(width, height, &robots))
     }
@@ -226,26 +228,39 @@ impl Scene {
             (false, false) => BlobDimension::None,
         }
     }
+}
 
-    #[allow(dead_code)]
-    fn print(&self) {
-        const FACTOR: usize = 3;
-        const THRESHOLD: usize = 1;
-
-        let mut grid = vec![vec![0; self.width / FACTOR + 1]; self.height / FACTOR + 1];
+impl Simulation for Scene {
+    fn step(&mut self) -> bool {
+        self.step_n(1);
+        true
+    }
 
+    /// The robots, counted per tile as in the puzzle description.
+    fn frame(&self) -> Frame {
+        let mut counts = vec![0; self.width * self.height];
         for robot in &self.robots {
-            grid[robot.y as usize / FACTOR][robot.x as usize / FACTOR] += 1;
+            counts[robot.y as usize * self.width + robot.x as usize] += 1;
         }
 
-        for row in grid {
-            println!(
-                "{}",
-                row.iter()
-                    .map(|c| if *c < THRESHOLD { ' ' } else { '*' })
-                    .collect::<String>()
-            );
+        let mut frame = Frame::new(self.width, self.height);
+        for (i, &count) in counts.iter().enumerate() {
+            let cell = match count {
+                0 => Cell::plain('.'),
+                n => Cell::coloured(char::from_digit(n, 10).unwrap_or('*'), Colour::Green),
+            };
+            frame.set((i / self.width, i % self.width), cell);
         }
+        frame.with_caption(format!("{} seconds", self.seconds))
+    }
+
+    fn inspect(&self) -> String {
+        format!(
+            "{} seconds, safety factor {}, blob {:?}",
+            self.seconds,
+            self.safety_factor(),
+            self.detect_blob()
+        )
     }
 }
 
@@ -269,12 +284,22 @@ p=9,5 v=-3,-3
 ";
 
     #[test]
-    fn test_print() {
+    fn test_frame() {
         let mut scene = Scene::parse(11, 7, &SAMPLE.into()).unwrap();
-        scene.print();
         scene.step_n(100);
-        println!();
-        scene.print();
+        assert_eq!(
+            scene.frame().to_string(),
+            "\
+......2..1.
+...........
+1..........
+.11........
+.....1.....
+...12......
+.1....1....
+100 seconds
+"
+        );
     }
 
     #[test]
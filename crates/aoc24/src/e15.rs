@@ -1,28 +1,39 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, Input, InputFetcher, PuzzleResult};
+use aoc::sim::Simulation;
+use aoc::{sim, viz, Answer, Input, Part, PuzzleResult, Solution};
 
-const DAY: Day = Day(15);
+pub struct WarehouseWoes;
 
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Warehouse Woes");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2024, day = 15, title = "Warehouse Woes")]
+impl Solution for WarehouseWoes {
+    type Parsed<'a> = &'a str;
 
-    let p1 = part1(&input)?;
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 1457740);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
+
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part1(&(*input).into())?.into())
+    }
 
-    let p2 = part2(&input)?;
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 1467145);
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part2(&(*input).into())?.into())
+    }
 
-    Ok(())
+    fn debug(&self, input: &&str, part: Part) -> PuzzleResult<()> {
+        let input: Input = (*input).into();
+        match part {
+            Part::One => sim::debug(part_1::Scene::<128>::parse(input.lines()?)?),
+            Part::Two => sim::debug(part_2::Scene::<256>::parse(input.lines()?)?),
+        }
+    }
 }
 
 fn part1(input: &Input) -> PuzzleResult<usize> {
     let lines = input.lines()?;
     let mut scene = part_1::Scene::<128>::parse(lines)?;
+    viz::record(|| scene.frame());
     while scene.step() {
-        // Just step
+        viz::record(|| scene.frame());
     }
 
     Ok(scene.coordinate_sum())
@@ -31,8 +42,9 @@ fn part1(input: &Input) -> PuzzleResult<usize> {
 fn part2(input: &Input) -> PuzzleResult<usize> {
     let lines = input.lines()?;
     let mut scene = part_2::Scene::<256>::parse(lines)?;
+    viz::record(|| scene.frame());
     while scene.step() {
-        // Just step
+        viz::record(|| scene.frame());
     }
 
     Ok(scene.coordinate_sum())
@@ -40,7 +52,9 @@ fn part2(input: &Input) -> PuzzleResult<usize> {
 
 mod part_1 {
     use aoc::input::Lines;
-    use aoc::PuzzleResult;
+    use aoc::sim::Simulation;
+    use aoc::viz::{Cell, Colour, Frame};
+    use aoc::{Direction, PuzzleResult};
     use std::collections::VecDeque;
 
     #[derive(Debug, Clone, Copy, PartialEq)]
@@ -51,14 +65,6 @@ mod part_1 {
         Wall,
     }
 
-    #[derive(Debug, Clone, Copy, PartialEq)]
-    enum Direction {
-        North,
-        East,
-        South,
-        West,
-    }
-
     #[derive(Debug, Clone, PartialEq)]
     struct Robot {
         x: usize,
@@ -104,14 +110,7 @@ mod part_1 {
             let mut moves = VecDeque::new();
             for line in lines {
                 for d in line.chars() {
-                    let d = match d {
-                        '^' => Direction::North,
-                        'v' => Direction::South,
-                        '<' => Direction::West,
-                        '>' => Direction::East,
-                        _ => panic!("unexpected character: {}", d),
-                    };
-                    moves.push_back(d);
+                    moves.push_back(Direction::try_from(d)?);
                 }
             }
 
@@ -125,37 +124,6 @@ mod part_1 {
             })
         }
 
-        pub(crate) fn step(&mut self) -> bool {
-            match self.moves.pop_front() {
-                Some(direction) => {
-                    assert!(0 < self.robot.x && self.robot.x < self.width - 1);
-                    assert!(0 < self.robot.y && self.robot.y < self.height - 1);
-
-                    let (next_x, next_y) = match direction {
-                        Direction::North => (self.robot.x, self.robot.y - 1),
-                        Direction::South => (self.robot.x, self.robot.y + 1),
-                        Direction::West => (self.robot.x - 1, self.robot.y),
-                        Direction::East => (self.robot.x + 1, self.robot.y),
-                    };
-
-                    let tile_free = match self.grid[next_y][next_x] {
-                        Tile::Empty => true,
-                        Tile::Wall => false,
-                        Tile::Box => self.try_push_boxes(direction, next_x, next_y),
-                        Tile::OutOfBounds => panic!("out of bounds"),
-                    };
-
-                    if tile_free {
-                        self.robot.x = next_x;
-                        self.robot.y = next_y;
-                    }
-
-                    true
-                }
-                None => false,
-            }
-        }
-
         fn try_push_boxes(&mut self, d: Direction, x: usize, y: usize) -> bool {
             let (mut bx, mut by) = (x, y);
             loop {
@@ -192,25 +160,69 @@ mod part_1 {
             }
             sum
         }
+    }
+
+    impl<const N: usize> Simulation for Scene<N> {
+        fn step(&mut self) -> bool {
+            match self.moves.pop_front() {
+                Some(direction) => {
+                    assert!(0 < self.robot.x && self.robot.x < self.width - 1);
+                    assert!(0 < self.robot.y && self.robot.y < self.height - 1);
+
+                    let (next_x, next_y) = match direction {
+                        Direction::North => (self.robot.x, self.robot.y - 1),
+                        Direction::South => (self.robot.x, self.robot.y + 1),
+                        Direction::West => (self.robot.x - 1, self.robot.y),
+                        Direction::East => (self.robot.x + 1, self.robot.y),
+                    };
+
+                    let tile_free = match self.grid[next_y][next_x] {
+                        Tile::Empty => true,
+                        Tile::Wall => false,
+                        Tile::Box => self.try_push_boxes(direction, next_x, next_y),
+                        Tile::OutOfBounds => panic!("out of bounds"),
+                    };
+
+                    if tile_free {
+                        self.robot.x = next_x;
+                        self.robot.y = next_y;
+                    }
+
+                    true
+                }
+                None => false,
+            }
+        }
 
-        #[allow(dead_code)]
-        fn print_grid(&self) {
+        fn frame(&self) -> Frame {
+            let mut frame = Frame::new(self.width, self.height);
             for y in 0..self.height {
                 for x in 0..self.width {
-                    if (x, y) == (self.robot.x, self.robot.y) {
-                        print!("@");
-                        continue;
-                    }
-                    let c = match self.grid[y][x] {
-                        Tile::Empty => '.',
-                        Tile::Wall => '#',
-                        Tile::Box => 'O',
-                        Tile::OutOfBounds => panic!("out of bounds"),
+                    let cell = match self.grid[y][x] {
+                        Tile::Empty => Cell::plain('.'),
+                        Tile::Wall => Cell::coloured('#', Colour::Grey),
+                        Tile::Box => Cell::coloured('O', Colour::Yellow),
+                        Tile::OutOfBounds => Cell::plain(' '),
                     };
-                    print!("{}", c);
+                    frame.set((y, x), cell);
                 }
-                println!();
             }
+            frame.set(
+                (self.robot.y, self.robot.x),
+                Cell::coloured('@', Colour::Red),
+            );
+            frame.with_caption(format!("{} moves left", self.moves.len()))
+        }
+
+        fn inspect(&self) -> String {
+            let next = self.moves.front().map_or('-', |d| d.arrow());
+            format!(
+                "robot ({}, {}), next move {}, GPS sum {}",
+                self.robot.x,
+                self.robot.y,
+                next,
+                self.coordinate_sum()
+            )
         }
     }
 
@@ -224,8 +236,15 @@ mod part_1 {
             let input: Input = super::super::tests::SAMPLE_1.into();
             let lines = input.lines().unwrap();
             let scene = Scene::<10>::parse(lines).unwrap();
-            println!("{:#?}", scene);
-            scene.print_grid();
+            assert_eq!((scene.width, scene.height), (8, 8));
+            assert_eq!(scene.moves.len(), 15);
+            assert!(scene.frame().to_string().starts_with(
+                "\
+########
+#..O.O.#
+##@.O..#
+"
+            ));
         }
 
         #[test]
@@ -233,24 +252,31 @@ mod part_1 {
             let input: Input = super::super::tests::SAMPLE_1.into();
             let lines = input.lines().unwrap();
             let mut scene = Scene::<10>::parse(lines).unwrap();
+            while scene.step() {}
 
-            println!("Start");
-            scene.print_grid();
-
-            let mut step = 0;
-            while !scene.moves.is_empty() {
-                println!("\n Step {step}: {:?}", scene.moves.front().unwrap());
-                scene.step();
-                scene.print_grid();
-                step += 1;
-            }
+            assert_eq!(
+                scene.frame().to_string(),
+                "\
+########
+#....OO#
+##.....#
+#.....O#
+#.#O@..#
+#...O..#
+#...O..#
+########
+0 moves left
+"
+            );
         }
     }
 }
 
 mod part_2 {
     use aoc::input::Lines;
-    use aoc::PuzzleResult;
+    use aoc::sim::Simulation;
+    use aoc::viz::{Cell, Colour, Frame};
+    use aoc::{Direction, PuzzleResult};
     use std::collections::VecDeque;
 
     #[derive(Debug, Clone, Copy, PartialEq)]
@@ -262,14 +288,6 @@ mod part_2 {
         Wall,
     }
 
-    #[derive(Debug, Clone, Copy, PartialEq)]
-    enum Direction {
-        North,
-        East,
-        South,
-        West,
-    }
-
     #[derive(Debug, Clone, Copy)]
     enum TileSide {
         Left,
@@ -322,14 +340,7 @@ mod part_2 {
             let mut moves = VecDeque::new();
             for line in lines {
                 for d in line.chars() {
-                    let d = match d {
-                        '^' => Direction::North,
-                        'v' => Direction::South,
-                        '<' => Direction::West,
-                        '>' => Direction::East,
-                        _ => panic!("unexpected character: {}", d),
-                    };
-                    moves.push_back(d);
+                    moves.push_back(Direction::try_from(d)?);
                 }
             }
 
@@ -412,7 +423,21 @@ mod part_2 {
             }
         }
 
-        pub(crate) fn step(&mut self) -> bool {
+        pub(crate) fn coordinate_sum(&self) -> usize {
+            let mut sum = 0;
+            for y in 0..self.height {
+                for x in 0..self.width {
+                    if let Tile::BoxLeft = self.grid[y][x] {
+                        sum += x + 100 * y;
+                    }
+                }
+            }
+            sum
+        }
+    }
+
+    impl<const N: usize> Simulation for Scene<N> {
+        fn step(&mut self) -> bool {
             match self.moves.pop_front() {
                 Some(direction) => {
                     let (x, y) = match direction {
@@ -442,37 +467,36 @@ mod part_2 {
             }
         }
 
-        pub(crate) fn coordinate_sum(&self) -> usize {
-            let mut sum = 0;
+        fn frame(&self) -> Frame {
+            let mut frame = Frame::new(self.width, self.height);
             for y in 0..self.height {
                 for x in 0..self.width {
-                    if let Tile::BoxLeft = self.grid[y][x] {
-                        sum += x + 100 * y;
-                    }
+                    let cell = match self.grid[y][x] {
+                        Tile::Empty => Cell::plain('.'),
+                        Tile::Wall => Cell::coloured('#', Colour::Grey),
+                        Tile::BoxLeft => Cell::coloured('[', Colour::Yellow),
+                        Tile::BoxRight => Cell::coloured(']', Colour::Yellow),
+                        Tile::OutOfBounds => Cell::plain(' '),
+                    };
+                    frame.set((y, x), cell);
                 }
             }
-            sum
+            frame.set(
+                (self.robot.y, self.robot.x),
+                Cell::coloured('@', Colour::Red),
+            );
+            frame.with_caption(format!("{} moves left", self.moves.len()))
         }
 
-        #[allow(dead_code)]
-        fn print_grid(&self) {
-            for y in 0..self.height {
-                for x in 0..self.width {
-                    if (x, y) == (self.robot.x, self.robot.y) {
-                        print!("@");
-                        continue;
-                    }
-                    let c = match self.grid[y][x] {
-                        Tile::Empty => '.',
-                        Tile::Wall => '#',
-                        Tile::BoxLeft => '[',
-                        Tile::BoxRight => ']',
-                        Tile::OutOfBounds => panic!("out of bounds"),
-                    };
-                    print!("{}", c);
-                }
-                println!();
-            }
+        fn inspect(&self) -> String {
+            let next = self.moves.front().map_or('-', |d| d.arrow());
+            format!(
+                "robot ({}, {}), next move {}, GPS sum {}",
+                self.robot.x,
+                self.robot.y,
+                next,
+                self.coordinate_sum()
+            )
         }
     }
 
@@ -486,8 +510,21 @@ mod part_2 {
             let input: Input = super::super::tests::SAMPLE.into();
             let lines = input.lines().unwrap();
             let scene = Scene::<20>::parse(lines).unwrap();
-            println!("{:#?}", scene);
-            scene.print_grid();
+            assert_eq!((scene.width, scene.height), (20, 10));
+            assert!(scene.frame().to_string().starts_with(
+                "\
+####################
+##....[]....[]..[]##
+##............[]..##
+##..[][]....[]..[]##
+##....[]@.....[]..##
+##[]##....[]......##
+##[]....[]....[]..##
+##..[][]..[]..[][]##
+##........[]......##
+####################
+"
+            ));
         }
 
         #[test]
@@ -495,17 +532,24 @@ mod part_2 {
             let input: Input = super::super::tests::SAMPLE.into();
             let lines = input.lines().unwrap();
             let mut scene = Scene::<20>::parse(lines).unwrap();
-
-            println!("Start");
-            scene.print_grid();
-
-            let mut step = 0;
-            while !scene.moves.is_empty() {
-                println!("\n Step {step}: {:?}", scene.moves.front().unwrap());
-                scene.step();
-                scene.print_grid();
-                step += 1;
-            }
+            while scene.step() {}
+
+            assert_eq!(
+                scene.frame().to_string(),
+                "\
+####################
+##[].......[].[][]##
+##[]...........[].##
+##[]........[][][]##
+##[]......[]....[]##
+##..##......[]....##
+##..[]............##
+##..@......[].[][]##
+##......[][]..[]..##
+####################
+0 moves left
+"
+            );
         }
     }
 }
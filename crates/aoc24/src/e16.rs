@@ -1,292 +1,129 @@
-use crate::e16::Direction::{East, West};
-use crate::YEAR;
-use aoc::{head, AocCache, Day, Input, InputFetcher, PuzzleError, PuzzleResult};
-use fxhash::FxHashSet;
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, VecDeque};
-use Direction::{North, South};
-
-const DAY: Day = Day(16);
-
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Reindeer Maze");
-    let input = aoc.get_input(YEAR, DAY)?;
-
-    let (p1, p2) = part_1_and_2(&input)?;
+use aoc::search::Searched;
+use aoc::viz::{Cell, Colour, Frame};
+use aoc::Direction::East;
+use aoc::{
+    search, viz, Answer, Direction, Grid, Input, Point, PuzzleError, PuzzleResult, Solution,
+};
+use std::collections::HashSet;
+
+pub struct ReindeerMaze;
+
+#[aoc::solution(year = 2024, day = 16, title = "Reindeer Maze")]
+impl Solution for ReindeerMaze {
+    type Parsed<'a> = Paths;
+
+    /// Both parts are about the best paths, so the maze is searched once here.
+    fn parse(&self, input: &Input) -> PuzzleResult<Paths> {
+        let maze = Maze::parse(input.text()?)?;
+        let searched = maze.search();
+        Ok(Paths { maze, searched })
+    }
 
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 72428);
+    fn part1(&self, paths: &Paths) -> PuzzleResult<Answer> {
+        Ok(part1(paths)?.into())
+    }
 
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 456);
+    fn part2(&self, paths: &Paths) -> PuzzleResult<Answer> {
+        Ok(part2(paths).into())
+    }
+}
 
-    Ok(())
+fn part1(paths: &Paths) -> PuzzleResult<Score> {
+    paths
+        .searched
+        .cost()
+        .ok_or(PuzzleError::Solution("No path found".to_string()))
 }
 
-fn part_1_and_2(input: &Input) -> Result<(Score, usize), PuzzleError> {
-    let maze = Maze::<142>::parse(&input.read_to_string()?);
-    let reindeer = Reindeer::new(maze.start.0, maze.start.1);
-    let (p1, p2) =
-        dijkstra(&reindeer, &maze).ok_or(PuzzleError::Solution("No path found".to_string()))?;
+fn part2(paths: &Paths) -> usize {
+    let tiles: HashSet<Point> = paths
+        .searched
+        .on_shortest_paths()
+        .into_iter()
+        .map(|reindeer| reindeer.pos)
+        .collect();
+    viz::record(|| paths.maze.frame(&tiles));
 
-    Ok((p1, p2))
+    tiles.len()
 }
 
-struct Maze<const N: usize> {
-    grid: [[char; N]; N],
-    width: usize,
-    height: usize,
-    start: (usize, usize),
-    end: (usize, usize),
+/// The maze with every state searched from the start until the end was reached.
+pub struct Paths {
+    maze: Maze,
+    searched: Searched<Reindeer, Score>,
 }
 
-impl<const N: usize> Maze<N> {
-    fn parse(input: &str) -> Self {
-        let mut grid = [[' '; N]; N];
-        let mut start: Option<(usize, usize)> = None;
-        let mut end: Option<(usize, usize)> = None;
-
-        for (r, line) in input.lines().enumerate() {
-            for (c, ch) in line.chars().enumerate() {
-                grid[r][c] = ch;
-                match ch {
-                    'E' => {
-                        assert_eq!(end, None, "Multiple ends found in maze");
-                        end = Some((r, c));
-                    }
-                    'S' => {
-                        assert_eq!(start, None, "Multiple starts found in maze");
-                        start = Some((r, c));
-                    }
-                    _ => {}
-                }
-            }
-        }
-
-        let height = input.lines().count();
-        let width = input
-            .lines()
-            .map(|line| line.len())
-            .max()
-            .expect("Empty maze");
+struct Maze {
+    grid: Grid<char>,
+    start: Point,
+    end: Point,
+}
 
-        Maze {
+impl Maze {
+    fn parse(input: &str) -> PuzzleResult<Self> {
+        let grid = Grid::parse(input, |ch| ch)?;
+        let start = grid
+            .find(|&ch| ch == 'S')
+            .ok_or_else(|| PuzzleError::Input("No start found in maze".to_string()))?;
+        let end = grid
+            .find(|&ch| ch == 'E')
+            .ok_or_else(|| PuzzleError::Input("No end found in maze".to_string()))?;
+
+        Ok(Maze {
             grid,
-            width,
-            height,
-            start: start.expect("No start found in maze"),
-            end: end.expect("No end found in maze"),
-        }
+            start: start.into(),
+            end: end.into(),
+        })
     }
 
-    #[allow(dead_code)]
-    fn print(&self) {
-        for r in 0..self.height {
-            for c in 0..self.width {
-                print!("{}", self.grid[r][c]);
-            }
-            println!();
-        }
+    fn search(&self) -> Searched<Reindeer, Score> {
+        let start = Reindeer {
+            pos: self.start,
+            direction: East,
+        };
+
+        search::dijkstra(
+            start,
+            |&Reindeer { pos, direction }| {
+                let ahead = pos + direction;
+                let forward = (self.grid[ahead] != '#').then_some((
+                    Reindeer {
+                        pos: ahead,
+                        direction,
+                    },
+                    1,
+                ));
+                let turns = [direction.turn_left(), direction.turn_right()]
+                    .map(|direction| (Reindeer { pos, direction }, 1000));
+                forward.into_iter().chain(turns)
+            },
+            |reindeer| reindeer.pos == self.end,
+        )
     }
 
-    #[allow(dead_code)]
-    fn print_with_path(&self, scores: &ScoreGrid<N>) {
-        // Since grid implements Copy, we don't need to clone.
-        let mut grid = self.grid;
-        scores.iter().enumerate().for_each(|(r, row)| {
-            row.iter().enumerate().for_each(|(c, col)| {
-                if col.iter().any(|&s| s < Score::MAX) {
-                    grid[r][c] = '*';
-                }
-            });
+    /// The maze with the tiles on `path` marked.
+    fn frame(&self, path: &HashSet<Point>) -> Frame {
+        let mut frame = Frame::from_grid(&self.grid, |&tile| match tile {
+            '#' => Cell::coloured('#', Colour::Grey),
+            'S' | 'E' => Cell::coloured(tile, Colour::Green),
+            tile => Cell::plain(tile),
         });
-
-        for row in grid.iter().take(self.height) {
-            for element in row.iter().take(self.width) {
-                let tile = match element {
-                    '.' => ' ',
-                    or => *or,
-                };
-                print!("{} ", tile);
+        for pos in path.iter().filter_map(|p| p.pos()) {
+            if !matches!(self.grid.get(pos), Some('S' | 'E')) {
+                frame.set(pos, Cell::coloured('O', Colour::Yellow));
             }
-            println!();
         }
+        frame
     }
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-enum Direction {
-    North,
-    South,
-    West,
-    East,
-}
-
-impl From<Direction> for usize {
-    fn from(d: Direction) -> usize {
-        match d {
-            North => 0,
-            South => 1,
-            West => 2,
-            East => 3,
-        }
-    }
-}
-
-#[derive(Clone, Copy, Debug, PartialEq)]
 struct Reindeer {
-    r: usize,
-    c: usize,
+    pos: Point,
     direction: Direction,
 }
 
-impl Reindeer {
-    fn new(r: usize, c: usize) -> Self {
-        Reindeer {
-            r,
-            c,
-            direction: East,
-        }
-    }
-}
-
 type Score = u32;
-type ScoreGrid<const N: usize> = [[[Score; 4]; N]; N];
-
-#[derive(Debug, Eq, PartialEq)]
-struct Step {
-    r: usize,
-    c: usize,
-    direction: Direction,
-    score: Score,
-}
-
-impl Ord for Step {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.score.cmp(&self.score)
-    }
-}
-
-impl PartialOrd<Self> for Step {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-fn dijkstra<const N: usize>(reindeer: &Reindeer, maze: &Maze<N>) -> Option<(Score, usize)> {
-    let try_visit = |r: usize,
-                     c: usize,
-                     direction: Direction,
-                     score: Score,
-                     scores: &mut ScoreGrid<N>,
-                     queue: &mut BinaryHeap<Step>| {
-        if maze.grid[r][c] == '#' || scores[r][c][usize::from(direction)] <= score {
-            return;
-        }
-
-        scores[r][c][usize::from(direction)] = score;
-        queue.push(Step {
-            r,
-            c,
-            direction,
-            score,
-        });
-    };
-
-    let mut queue = BinaryHeap::<Step>::new();
-    let mut scores = [[[Score::MAX; 4]; N]; N];
-    try_visit(
-        reindeer.r,
-        reindeer.c,
-        reindeer.direction,
-        0,
-        &mut scores,
-        &mut queue,
-    );
-
-    while let Some(Step {
-        r,
-        c,
-        direction,
-        score,
-    }) = queue.pop()
-    {
-        if scores[r][c][usize::from(direction)] < score {
-            continue;
-        }
-
-        if maze.grid[r][c] == 'E' {
-            let tile_count = backtrace(&scores, maze.end, direction);
-            return Some((score, tile_count));
-        }
-
-        match direction {
-            North => {
-                try_visit(r - 1, c, direction, score + 1, &mut scores, &mut queue);
-                try_visit(r, c, West, score + 1000, &mut scores, &mut queue);
-                try_visit(r, c, East, score + 1000, &mut scores, &mut queue);
-            }
-            South => {
-                try_visit(r + 1, c, direction, score + 1, &mut scores, &mut queue);
-                try_visit(r, c, East, score + 1000, &mut scores, &mut queue);
-                try_visit(r, c, West, score + 1000, &mut scores, &mut queue);
-            }
-            West => {
-                try_visit(r, c - 1, direction, score + 1, &mut scores, &mut queue);
-                try_visit(r, c, South, score + 1000, &mut scores, &mut queue);
-                try_visit(r, c, North, score + 1000, &mut scores, &mut queue);
-            }
-            East => {
-                try_visit(r, c + 1, direction, score + 1, &mut scores, &mut queue);
-                try_visit(r, c, North, score + 1000, &mut scores, &mut queue);
-                try_visit(r, c, South, score + 1000, &mut scores, &mut queue);
-            }
-        };
-    }
-
-    None
-}
-
-fn backtrace<const N: usize>(
-    scores: &[[[Score; 4]; N]; N],
-    end: (usize, usize),
-    end_direction: Direction,
-) -> usize {
-    let mut queue = VecDeque::<(usize, usize, Direction)>::from([(end.0, end.1, end_direction)]);
-
-    let mut path = FxHashSet::<(usize, usize)>::default();
-
-    while let Some((r, c, direction)) = queue.pop_front() {
-        let score = scores[r][c][usize::from(direction)];
-        path.insert((r, c));
-        if score == 0 {
-            continue;
-        }
-
-        for d in &[North, South, West, East] {
-            if *d == direction {
-                continue;
-            }
-            let other_score = scores[r][c][usize::from(*d)];
-            if other_score == score - 1000 {
-                queue.push_back((r, c, *d));
-            }
-        }
-
-        let prev_pos = match direction {
-            North => (r + 1, c),
-            South => (r - 1, c),
-            West => (r, c + 1),
-            East => (r, c - 1),
-        };
-
-        if scores[prev_pos.0][prev_pos.1][usize::from(direction)] == score - 1 {
-            queue.push_back((prev_pos.0, prev_pos.1, direction));
-        }
-    }
-
-    path.len()
-}
 
 #[cfg(test)]
 mod tests {
@@ -331,22 +168,31 @@ mod tests {
 ";
 
     #[test]
-    #[ignore] // Manually verify output
     fn test_maze_parse() {
-        let maze = Maze::<20>::parse(SAMPLE_1);
-        maze.print();
-        println!("Start: {:?}", maze.start);
+        let maze = Maze::parse(SAMPLE_1).unwrap();
+        assert_eq!(maze.start, Point::from((13, 1)));
+        assert_eq!(maze.frame(&HashSet::new()).to_string(), SAMPLE_1);
+
+        let path = maze.search().path().unwrap();
+        let frame = maze.frame(&path.iter().map(|r| r.pos).collect());
+        let marked = frame.to_string().chars().filter(|&ch| ch == 'O').count();
+        // A score of 7036 is 36 steps, so 37 tiles with the start and the end
+        assert_eq!(marked, 37 - 2);
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(part_1_and_2(&SAMPLE_1.into()).unwrap().0, 7036);
-        assert_eq!(part_1_and_2(&SAMPLE_2.into()).unwrap().0, 11048);
+        let paths = ReindeerMaze.parse(&SAMPLE_1.into()).unwrap();
+        assert_eq!(part1(&paths).unwrap(), 7036);
+        let paths = ReindeerMaze.parse(&SAMPLE_2.into()).unwrap();
+        assert_eq!(part1(&paths).unwrap(), 11048);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part_1_and_2(&SAMPLE_1.into()).unwrap().1, 45);
-        assert_eq!(part_1_and_2(&SAMPLE_2.into()).unwrap().1, 64);
+        let paths = ReindeerMaze.parse(&SAMPLE_1.into()).unwrap();
+        assert_eq!(part2(&paths), 45);
+        let paths = ReindeerMaze.parse(&SAMPLE_2.into()).unwrap();
+        assert_eq!(part2(&paths), 64);
     }
 }
@@ -1,31 +1,32 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, Input, InputFetcher, PuzzleError, PuzzleResult};
+use aoc::{Answer, Input, PuzzleError, PuzzleResult, Solution};
 use itertools::Itertools;
 
-const DAY: Day = Day(17);
+pub struct ChronospatialComputer;
 
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Chronospatial Computer");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2024, day = 17, title = "Chronospatial Computer")]
+impl Solution for ChronospatialComputer {
+    type Parsed<'a> = &'a str;
 
-    let p1 = part1(&input)?;
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, "1,3,7,4,6,4,2,3,5");
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let p2 = part2(&input)?;
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 202_367_025_818_154);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part1(&(*input).into())?.into())
+    }
 
-    Ok(())
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part2(&(*input).into())?.into())
+    }
 }
 
 fn part1(input: &Input) -> PuzzleResult<String> {
-    let mut computer = ChronospatialComputer::parse(&input.read_to_string()?);
+    let mut computer = Computer::parse(input.text()?);
     Ok(computer.execute().iter().join(","))
 }
 
 fn part2(input: &Input) -> PuzzleResult<Value> {
-    let computer = ChronospatialComputer::parse(&input.read_to_string()?);
+    let computer = Computer::parse(input.text()?);
 
     fn to_value(cs: &[u8]) -> Value {
         cs.iter().fold(0, |value, &x| (value << 3) | x as Value)
@@ -101,14 +102,14 @@ const REG_B: usize = 1;
 const REG_C: usize = 2;
 
 #[derive(Clone, Debug)]
-struct ChronospatialComputer {
+struct Computer {
     registers: [Value; 3],
     program: Vec<(Instruction, u8)>,
     opcodes: Vec<u8>,
     program_counter: usize,
 }
 
-impl ChronospatialComputer {
+impl Computer {
     fn parse(input: &str) -> Self {
         let mut registers = [0; 3];
 
@@ -239,7 +240,7 @@ Program: 0,3,5,4,3,0
 ";
     #[test]
     fn test_parse() {
-        let computer = ChronospatialComputer::parse(SAMPLE);
+        let computer = Computer::parse(SAMPLE);
         assert_eq!(computer.registers, [729, 0, 0]);
         assert_eq!(
             computer.program,
@@ -1,41 +1,45 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, Input, InputFetcher, Lines, PuzzleError, PuzzleResult};
-use std::cmp::Reverse;
-use std::collections::BinaryHeap;
+use aoc::grid::Pos;
+use aoc::search::Searched;
+use aoc::viz::{Cell, Colour, Frame};
+use aoc::{search, viz, Answer, Grid, Input, Lines, PuzzleError, PuzzleResult, Solution};
 
-const DAY: Day = Day(18);
+pub struct RamRun;
 
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "RAM Run");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2024, day = 18, title = "RAM Run")]
+impl Solution for RamRun {
+    type Parsed<'a> = &'a str;
 
-    let p1 = part1(&input, 1024, 71, 71)?;
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 324);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let p2 = part2(&input, 71, 71)?;
-    println!("Part 2: {},{}", p2.0, p2.1);
-    assert_eq!(p2, (46, 23));
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part1(&(*input).into(), 1024, 71, 71)?.into())
+    }
 
-    Ok(())
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        let p2 = part2(&(*input).into(), 71, 71)?;
+        Ok(format!("{},{}", p2.0, p2.1).into())
+    }
 }
 
-fn part1(input: &Input, max_bytes: usize, width: usize, height: usize) -> PuzzleResult<Score> {
-    let mut grid = Grid::parse(input.lines()?, width, height)?;
-    grid.drop_bytes(max_bytes);
-    Ok(grid.dijkstra().expect("No path found"))
+fn part1(input: &Input, max_bytes: usize, width: usize, height: usize) -> PuzzleResult<usize> {
+    let memory = Memory::parse(input.lines()?, width, height)?;
+    viz::record(|| memory.frame(max_bytes));
+    memory
+        .shortest_path(max_bytes)
+        .ok_or_else(|| PuzzleError::Solution("No path found".into()))
 }
 
 fn part2(input: &Input, width: usize, height: usize) -> PuzzleResult<(usize, usize)> {
-    let grid = Grid::parse(input.lines()?, width, height)?;
+    let memory = Memory::parse(input.lines()?, width, height)?;
     let mut low = 0;
-    let mut high = grid.coordinates.len();
+    let mut high = memory.coordinates.len();
 
     while low < high {
-        let mut grid = grid.clone();
         let mid = (low + high) / 2;
-        grid.drop_bytes(mid);
-        if grid.dijkstra().is_none() {
+        viz::record(|| memory.frame(mid));
+        if memory.shortest_path(mid).is_none() {
             high = mid;
         } else {
             low = mid + 1;
@@ -46,24 +50,16 @@ fn part2(input: &Input, width: usize, height: usize) -> PuzzleResult<(usize, usi
         return Err(PuzzleError::Solution("No solution found".into()));
     }
 
-    Ok(grid.coordinates[low - 1])
+    Ok(memory.coordinates[low - 1])
 }
 
-type Score = u16;
-const N: usize = 73;
-const BYTE_TILE: Score = Score::MAX;
-const UNVISITED_EMPTY_TILE: Score = Score::MAX - 1;
-
-#[derive(Clone)]
-struct Grid {
-    grid: [[Score; N]; N],
+struct Memory {
     width: usize,
     height: usize,
-    goal: (usize, usize),
     coordinates: Vec<(usize, usize)>,
 }
 
-impl Grid {
+impl Memory {
     fn parse(lines: Lines, width: usize, height: usize) -> PuzzleResult<Self> {
         let coordinates = lines
             .map(|line| {
@@ -80,88 +76,46 @@ impl Grid {
             })
             .collect::<Result<Vec<_>, _>>()?;
 
-        let mut grid = [[UNVISITED_EMPTY_TILE; N]; N];
-
-        for x in 0..width + 2 {
-            grid[0][x] = BYTE_TILE;
-            grid[height + 1][x] = BYTE_TILE;
-        }
-
-        for row in grid.iter_mut().take(height + 2) {
-            row[0] = BYTE_TILE;
-            row[width + 1] = BYTE_TILE;
-        }
-
-        grid[1][1] = 0;
-
         Ok(Self {
-            grid,
             width,
             height,
-            goal: (width, height),
             coordinates,
         })
     }
 
-    fn drop_bytes(&mut self, n: usize) {
-        for (x, y) in self.coordinates.iter().take(n) {
-            self.grid[*y + 1][*x + 1] = BYTE_TILE;
+    /// The grid with a wall where each of the first `n` bytes fell.
+    fn corrupted(&self, n: usize) -> Grid<bool> {
+        let mut grid = Grid::new(self.width, self.height, false);
+        for &(x, y) in self.coordinates.iter().take(n) {
+            grid[(y, x)] = true;
         }
+        grid
     }
 
-    fn dijkstra(&mut self) -> Option<Score> {
-        let mut queue = BinaryHeap::new();
-        for (y, line) in self.grid.iter().enumerate() {
-            for (x, score) in line.iter().enumerate() {
-                if *score != BYTE_TILE && *score != UNVISITED_EMPTY_TILE {
-                    queue.push(Reverse((*score, (x, y))));
-                }
-            }
-        }
-
-        while let Some(Reverse((score, (x, y)))) = queue.pop() {
-            if score == UNVISITED_EMPTY_TILE {
-                return None;
-            }
-
-            let grid_score = self.grid[y][x];
-            if grid_score < score {
-                continue;
-            }
-
-            let new_score = grid_score + 1;
-            let new_positions = [(x, y - 1), (x, y + 1), (x - 1, y), (x + 1, y)];
-            for (nx, ny) in new_positions {
-                if (nx, ny) == self.goal {
-                    self.grid[ny][nx] = new_score;
-                    return Some(new_score);
-                }
-                let new_grid_score = self.grid[ny][nx];
-                if new_grid_score == BYTE_TILE {
-                    continue;
-                }
-                if new_score < new_grid_score {
-                    self.grid[ny][nx] = new_score;
-                    queue.push(Reverse((new_score, (nx, ny))));
-                }
-            }
-        }
+    fn search(&self, grid: &Grid<bool>) -> Searched<Pos, usize> {
+        let goal = (self.height - 1, self.width - 1);
+        search::bfs(
+            (0, 0),
+            |&pos| grid.neighbours4(pos).filter(|&next| !grid[next]),
+            |&pos| pos == goal,
+        )
+    }
 
-        None
+    fn shortest_path(&self, fallen: usize) -> Option<usize> {
+        self.search(&self.corrupted(fallen)).cost()
     }
 
-    #[allow(dead_code)]
-    fn print(&self) {
-        for y in 0..self.height + 2 {
-            for x in 0..self.width + 2 {
-                match self.grid[y][x] {
-                    BYTE_TILE => print!("#"),
-                    UNVISITED_EMPTY_TILE => print!(" "),
-                    score => print!("{:1}", score % 10),
-                }
-            }
-            println!();
+    /// The memory after `fallen` bytes, with a shortest path if there is one.
+    fn frame(&self, fallen: usize) -> Frame {
+        let grid = self.corrupted(fallen);
+        let mut frame = Frame::from_grid(&grid, |&byte| match byte {
+            true => Cell::coloured('#', Colour::Red),
+            false => Cell::plain('.'),
+        });
+        for &pos in self.search(&grid).path().unwrap_or_default().iter() {
+            frame.set(pos, Cell::coloured('O', Colour::Yellow));
         }
+        frame.with_caption(format!("{} bytes", fallen))
     }
 }
 
@@ -197,6 +151,14 @@ mod tests {
 2,0
 ";
 
+    #[test]
+    fn test_frame() {
+        let memory = Memory::parse(Input::from(SAMPLE).lines().unwrap(), 7, 7).unwrap();
+        let text = memory.frame(12).to_string();
+        assert_eq!(text.lines().next(), Some("OO.#OOO"));
+        assert_eq!(text.chars().filter(|&ch| ch == 'O').count(), 23);
+    }
+
     #[test]
     fn test_part1() {
         assert_eq!(part1(&SAMPLE.into(), 12, 7, 7).unwrap(), 22);
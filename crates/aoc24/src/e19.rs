@@ -1,26 +1,38 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, Input, InputFetcher, PuzzleResult};
+use aoc::{parse, Answer, Input, PuzzleError, PuzzleResult, Solution};
 use rayon::prelude::*;
 
-const DAY: Day = Day(19);
+pub struct LinenLayout;
 
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Linen Layout");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2024, day = 19, title = "Linen Layout")]
+impl Solution for LinenLayout {
+    type Parsed<'a> = Vec<usize>;
 
-    let (p1, p2) = part_1_and_2(&input)?;
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 363);
+    /// Both parts count the arrangements of each pattern, so they are counted once here.
+    fn parse(&self, input: &Input) -> PuzzleResult<Vec<usize>> {
+        Ok(arrangements(&Linen::try_from(input.text()?)?))
+    }
 
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 642535800868438);
+    fn part1(&self, arrangements: &Vec<usize>) -> PuzzleResult<Answer> {
+        Ok(part1(arrangements).into())
+    }
 
-    Ok(())
+    fn part2(&self, arrangements: &Vec<usize>) -> PuzzleResult<Answer> {
+        Ok(part2(arrangements).into())
+    }
 }
 
-fn part_1_and_2(input: &Input) -> PuzzleResult<(usize, usize)> {
-    let linen = Linen::from(input.read_to_string()?.as_str());
+/// The number of patterns that can be made at all.
+fn part1(arrangements: &[usize]) -> usize {
+    arrangements.iter().filter(|&&n| n > 0).count()
+}
 
+/// The number of ways all the patterns can be made.
+fn part2(arrangements: &[usize]) -> usize {
+    arrangements.iter().sum()
+}
+
+/// The number of ways each requested pattern can be made from the towels.
+fn arrangements(linen: &Linen) -> Vec<usize> {
     let mut towels: Vec<Vec<(u128, usize)>> = vec![Vec::new(); 8];
     linen
         .towels
@@ -31,20 +43,11 @@ fn part_1_and_2(input: &Input) -> PuzzleResult<(usize, usize)> {
             towels[towel_ind].push((towel, len));
         });
 
-    let (count, sum) = linen
+    linen
         .requested_patterns
         .par_iter()
-        .map(|p| (encode_string(p), p.len()))
-        .map(|(pattern, pattern_len)| {
-            let result = match_pattern(pattern, pattern_len, &towels, &mut [usize::MAX; 84]);
-            ((result > 0) as usize, result)
-        })
-        .reduce(
-            || (0, 0),
-            |(count1, sum1), (count2, sum2)| (count1 + count2, sum1 + sum2),
-        );
-
-    Ok((count, sum))
+        .map(|p| match_pattern(encode_string(p), p.len(), &towels, &mut [usize::MAX; 84]))
+        .collect()
 }
 
 const WHITE: u8 = 0;
@@ -147,13 +150,20 @@ struct Linen {
     requested_patterns: Vec<String>,
 }
 
-impl From<&str> for Linen {
-    fn from(input: &str) -> Self {
-        let (towels, requested_patterns) = input.split_once("\n\n").expect("No empty line");
-        Self {
+impl TryFrom<&str> for Linen {
+    type Error = PuzzleError;
+
+    fn try_from(input: &str) -> PuzzleResult<Self> {
+        let [towels, requested_patterns] = parse::sections(input)[..] else {
+            return Err(PuzzleError::Input(
+                "Expected towels and requested patterns".to_string(),
+            ));
+        };
+
+        Ok(Self {
             towels: towels.split(", ").map(str::to_string).collect(),
             requested_patterns: requested_patterns.lines().map(str::to_string).collect(),
-        }
+        })
     }
 }
 
@@ -176,9 +186,9 @@ bbrgwb
 
     #[test]
     fn test_parts() {
-        let (p1, p2) = part_1_and_2(&SAMPLE.into()).unwrap();
-        assert_eq!(p1, 6);
-        assert_eq!(p2, 16);
+        let arrangements = LinenLayout.parse(&SAMPLE.into()).unwrap();
+        assert_eq!(part1(&arrangements), 6);
+        assert_eq!(part2(&arrangements), 16);
     }
 
     #[test]
@@ -1,24 +1,24 @@
-use crate::YEAR;
-use aoc::{head, AocCache, Day, Input, InputFetcher, PuzzleResult};
+use aoc::{search, Answer, Grid, Input, PuzzleError, PuzzleResult, Solution};
 use rayon::prelude::*;
-use std::collections::VecDeque;
 use std::fmt::{Display, Formatter};
 
-const DAY: Day = Day(20);
+pub struct RaceCondition;
 
-pub fn solve(aoc: &AocCache) -> PuzzleResult<()> {
-    head(YEAR, DAY, "Race Condition");
-    let input = aoc.get_input(YEAR, DAY)?;
+#[aoc::solution(year = 2024, day = 20, title = "Race Condition")]
+impl Solution for RaceCondition {
+    type Parsed<'a> = &'a str;
 
-    let p1 = part1(&input)?;
-    println!("Part 1: {}", p1);
-    assert_eq!(p1, 1289);
+    fn parse<'a>(&self, input: &'a Input) -> PuzzleResult<&'a str> {
+        input.text()
+    }
 
-    let p2 = part2(&input)?;
-    println!("Part 2: {}", p2);
-    assert_eq!(p2, 982425);
+    fn part1(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part1(&(*input).into())?.into())
+    }
 
-    Ok(())
+    fn part2(&self, input: &&str) -> PuzzleResult<Answer> {
+        Ok(part2(&(*input).into())?.into())
+    }
 }
 
 fn part1(input: &Input) -> PuzzleResult<usize> {
@@ -30,19 +30,18 @@ fn part2(input: &Input) -> PuzzleResult<usize> {
 }
 
 fn count_shortcuts(input: &Input, shortcut_length: usize, threshold: usize) -> PuzzleResult<usize> {
-    let mut maze: Maze = Maze::from(input.read_to_string()?.as_str());
+    let mut maze = Maze::parse(input.text()?)?;
     maze.walk();
     let cheats = maze.find_cheats(shortcut_length, threshold);
     Ok(cheats)
 }
 
 type Tile = i16;
-const GRID_SIZE: usize = 142;
 const WALL_TILE: Tile = Tile::MAX;
 const EMPTY_TILE: Tile = Tile::MAX - 1;
 
 struct Maze {
-    grid: [[Tile; GRID_SIZE]; GRID_SIZE],
+    grid: Grid<Tile>,
     width: usize,
     height: usize,
     start: (usize, usize),
@@ -50,22 +49,45 @@ struct Maze {
 }
 
 impl Maze {
+    fn parse(input: &str) -> PuzzleResult<Self> {
+        let chars = Grid::try_parse(input, |c| match c {
+            '#' | '.' | 'S' | 'E' => Ok(c),
+            _ => Err(PuzzleError::Input(format!(
+                "Invalid character in maze: {c}"
+            ))),
+        })?;
+        let find = |tile: char| {
+            chars
+                .find(|&c| c == tile)
+                .map(|(y, x)| (x, y))
+                .ok_or_else(|| PuzzleError::Input(format!("No {tile} in the maze")))
+        };
+        let start = find('S')?;
+        let end = find('E')?;
+        let grid = chars.map(|&c| if c == '#' { WALL_TILE } else { EMPTY_TILE });
+
+        Ok(Self {
+            width: grid.width(),
+            height: grid.height(),
+            grid,
+            start,
+            end,
+        })
+    }
+
     fn walk(&mut self) {
-        let mut queue = VecDeque::from([self.start]);
-        self.grid[self.start.1][self.start.0] = 0;
-
-        while let Some((x, y)) = queue.pop_front() {
-            let next = [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)];
-            for (nx, ny) in next {
-                match self.grid[ny][nx] {
-                    WALL_TILE => continue,
-                    EMPTY_TILE => {
-                        self.grid[ny][nx] = self.grid[y][x] + 1;
-                        queue.push_back((nx, ny));
-                    }
-                    _ => {}
-                }
-            }
+        let grid = &self.grid;
+        let distances = search::bfs(
+            (self.start.1, self.start.0),
+            |&pos| {
+                grid.neighbours4(pos)
+                    .filter(|&next| grid[next] != WALL_TILE)
+            },
+            |_| false,
+        );
+
+        for (&pos, distance) in distances.reached() {
+            self.grid[pos] = distance as Tile;
         }
     }
 
@@ -87,7 +109,7 @@ impl Maze {
         let last_y = self.height as isize - 1;
         let last_x = self.width as isize - 1;
 
-        let tile = self.grid[y][x];
+        let tile = self.grid[(y, x)];
         if tile == WALL_TILE {
             return 0;
         }
@@ -104,7 +126,7 @@ impl Maze {
             let last = last_x.min(max_dx + x);
 
             for nx in first..=last {
-                let other_tile = self.grid[ny as usize][nx as usize];
+                let other_tile = self.grid[(ny as usize, nx as usize)];
                 if other_tile == WALL_TILE {
                     continue;
                 }
@@ -127,12 +149,12 @@ impl Display for Maze {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         for y in 0..self.height {
             for x in 0..self.width {
-                let c = match self.grid[y][x] {
+                let c = match self.grid[(y, x)] {
                     WALL_TILE => '#',
                     EMPTY_TILE if (x, y) == self.start => 'S',
                     EMPTY_TILE if (x, y) == self.end => 'E',
                     EMPTY_TILE => '.',
-                    _ => ((self.grid[y][x] % 10) as u8 + b'0') as char,
+                    _ => ((self.grid[(y, x)] % 10) as u8 + b'0') as char,
                 };
                 write!(f, "{}", c)?;
             }
@@ -142,42 +164,6 @@ impl Display for Maze {
     }
 }
 
-impl From<&str> for Maze {
-    fn from(input: &str) -> Self {
-        let mut grid = [[EMPTY_TILE; GRID_SIZE]; GRID_SIZE];
-        let mut start: Option<(usize, usize)> = None;
-        let mut end: Option<(usize, usize)> = None;
-
-        for (y, line) in input.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                grid[y][x] = match c {
-                    '#' => WALL_TILE,
-                    '.' => EMPTY_TILE,
-                    'S' => {
-                        assert_eq!(start, None, "Multiple start positions");
-                        start = Some((x, y));
-                        EMPTY_TILE
-                    }
-                    'E' => {
-                        assert_eq!(end, None, "Multiple end positions");
-                        end = Some((x, y));
-                        EMPTY_TILE
-                    }
-                    _ => panic!("Invalid character in maze: {}", c),
-                };
-            }
-        }
-
-        Self {
-            grid,
-            start: start.expect("No start position"),
-            end: end.expect("No end position"),
-            width: input.lines().next().unwrap().len(),
-            height: input.lines().count(),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -202,7 +188,7 @@ mod tests {
 
     #[test]
     fn test_parsing() {
-        let maze: Maze = SAMPLE.into();
+        let maze = Maze::parse(SAMPLE).unwrap();
         assert_eq!(maze.start, (1, 3));
         assert_eq!(maze.end, (5, 7));
         assert_eq!(maze.width, 15);
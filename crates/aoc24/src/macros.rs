@@ -1,8 +0,0 @@
-#[macro_export]
-macro_rules! run_solutions {
-    ($($module:ident),*) => {
-        run(&[
-            $( $module::solve, )*
-        ])
-    };
-}
@@ -0,0 +1,145 @@
+use aoc::bench::{merge, regressions, Table};
+use aoc::cli::{BenchArgs, BenchFormat, Command, DebugArgs, NewArgs, RunArgs, SubmitArgs};
+use aoc::scaffold::Workspace;
+use aoc::{AocCache, Cli, Registry};
+use clap::Parser;
+use log::LevelFilter;
+use simplelog::{ColorChoice, Config, TermLogger, TerminalMode};
+use std::error::Error;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    setup_log();
+
+    let cli = Cli::parse();
+    match go_nuts(cli) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("Failed to solve puzzles: {}", err.pretty());
+
+            let mut source = err.source();
+            while let Some(cause) = source {
+                eprintln!("  caused by: {}", cause);
+                source = cause.source();
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn setup_log() {
+    TermLogger::init(
+        LevelFilter::Off,
+        Config::default(),
+        TerminalMode::Mixed,
+        ColorChoice::Auto,
+    )
+    .unwrap();
+}
+
+// The years register their solutions when they are linked in
+use aoc15 as _;
+use aoc24 as _;
+
+fn registry() -> Registry {
+    Registry::registered()
+}
+
+/// Whether everything that was run passed.
+fn go_nuts(cli: Cli) -> aoc::PuzzleResult<bool> {
+    let cache = cli.cache();
+    match cli.command.unwrap_or_default() {
+        Command::Run(args) => run(&cache, &args),
+        Command::Submit(args) => submit(&cache, &args).map(|_| true),
+        Command::Bench(args) => bench(&cache, &args).map(|_| true),
+        Command::New(args) => new(&cache, &args).map(|_| true),
+        Command::Debug(args) => debug(&cache, &args).map(|_| true),
+    }
+}
+
+fn run(cache: &AocCache, args: &RunArgs) -> aoc::PuzzleResult<bool> {
+    let selection = args.selection()?;
+    let summary = aoc::run(&registry(), cache, &selection, &args.options())?;
+
+    println!("\n{}", summary);
+
+    Ok(summary.passed())
+}
+
+fn bench(cache: &AocCache, args: &BenchArgs) -> aoc::PuzzleResult<()> {
+    let selection = args.selection()?;
+    let benches = aoc::bench(&registry(), cache, &selection, &args.options())?;
+
+    let saved = cache.bench_results()?;
+    let regressions = regressions(&saved, &benches, args.threshold);
+
+    match args.format {
+        BenchFormat::Table => {
+            println!(
+                "\n{}",
+                Table {
+                    benches: &benches,
+                    regressions: &regressions,
+                }
+            );
+            if !regressions.is_empty() {
+                println!(
+                    "{} phases are more than {}% slower than last time",
+                    regressions.len(),
+                    args.threshold
+                );
+            }
+        }
+        BenchFormat::Json => {
+            let json = serde_json::to_string_pretty(&benches)
+                .map_err(|e| aoc::PuzzleError::Solution(format!("Can't serialize results: {e}")))?;
+            println!("{json}");
+        }
+    }
+
+    if !args.no_save {
+        cache.record_bench_results(&merge(saved, &benches))?;
+    }
+
+    Ok(())
+}
+
+fn new(cache: &AocCache, args: &NewArgs) -> aoc::PuzzleResult<()> {
+    let workspace = Workspace::find(&std::env::current_dir()?)?;
+    aoc::scaffold::new_day(cache, &workspace, args.year, args.day)
+}
+
+fn submit(cache: &AocCache, args: &SubmitArgs) -> aoc::PuzzleResult<()> {
+    let answer = args.answer.as_deref().map(Into::into);
+    let outcome = aoc::submit(&registry(), cache, args.year, args.day, args.part, answer)?;
+
+    println!("{}", outcome);
+
+    Ok(())
+}
+
+fn debug(cache: &AocCache, args: &DebugArgs) -> aoc::PuzzleResult<()> {
+    aoc::sim::debug_puzzle(
+        &registry(),
+        cache,
+        args.year,
+        args.day,
+        args.part,
+        args.sample,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let registry = registry();
+        assert_eq!(registry.years(), [2015, 2024]);
+        for year in [2015, 2024] {
+            assert_eq!(registry.days(year), (1..=20).collect::<Vec<_>>());
+        }
+    }
+}
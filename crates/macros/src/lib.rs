@@ -0,0 +1,270 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{bracketed, Ident, ImplItem, ItemImpl, LitInt, LitStr, Token, Type};
+
+/// Fills in the year, day and title of a `Solution` and registers it, so
+/// that the runner finds it without being told:
+///
+/// ```ignore
+/// pub struct GuardGallivant;
+///
+/// #[aoc::solution(year = 2024, day = 6, title = "Guard Gallivant")]
+/// impl Solution for GuardGallivant {
+///     type Parsed<'a> = &'a str;
+///     // parse, part1 and part2
+/// }
+/// ```
+///
+/// Parts can be tagged, so that they can be left out of a run, with
+/// `tags = [slow]` for both parts or `part1_tags` and `part2_tags` for one.
+///
+/// The solution must be a unit struct.
+#[proc_macro_attribute]
+pub fn solution(args: TokenStream, item: TokenStream) -> TokenStream {
+    expand(args.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct Args {
+    year: LitInt,
+    day: LitInt,
+    title: LitStr,
+    tags: [Vec<TokenStream2>; 2],
+}
+
+/// The `aoc::Tag` variants of a list like `[slow, broken]`.
+fn parse_tags(input: syn::parse::ParseStream) -> syn::Result<Vec<TokenStream2>> {
+    let content;
+    bracketed!(content in input);
+    let names = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+
+    names
+        .iter()
+        .map(|name| match name.to_string().as_str() {
+            "slow" => Ok(quote!(::aoc::Tag::Slow)),
+            "broken" => Ok(quote!(::aoc::Tag::Broken)),
+            "experimental" => Ok(quote!(::aoc::Tag::Experimental)),
+            _ => Err(syn::Error::new(
+                name.span(),
+                "expected `slow`, `broken` or `experimental`",
+            )),
+        })
+        .collect()
+}
+
+fn parse_args(args: TokenStream2) -> syn::Result<Args> {
+    let mut year = None;
+    let mut day = None;
+    let mut title = None;
+    let mut tags = [Vec::new(), Vec::new()];
+
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("year") {
+            let value: LitInt = meta.value()?.parse()?;
+            if value.base10_parse::<u16>()? < 2015 {
+                return Err(syn::Error::new(value.span(), "the first year is 2015"));
+            }
+            year = Some(value);
+        } else if meta.path.is_ident("day") {
+            let value: LitInt = meta.value()?.parse()?;
+            if value.base10_parse::<u8>()? > 25 {
+                return Err(syn::Error::new(value.span(), "the last day is 25"));
+            }
+            day = Some(value);
+        } else if meta.path.is_ident("title") {
+            title = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("tags") {
+            let both = parse_tags(meta.value()?)?;
+            tags.iter_mut().for_each(|part| part.extend(both.clone()));
+        } else if meta.path.is_ident("part1_tags") {
+            tags[0].extend(parse_tags(meta.value()?)?);
+        } else if meta.path.is_ident("part2_tags") {
+            tags[1].extend(parse_tags(meta.value()?)?);
+        } else {
+            return Err(meta.error("expected `year`, `day`, `title` or tags"));
+        }
+        Ok(())
+    });
+    parser.parse2(args.clone())?;
+
+    let missing = |name| syn::Error::new_spanned(&args, format!("missing `{name}`"));
+    Ok(Args {
+        year: year.ok_or_else(|| missing("year"))?,
+        day: day.ok_or_else(|| missing("day"))?,
+        title: title.ok_or_else(|| missing("title"))?,
+        tags,
+    })
+}
+
+fn expand(args: TokenStream2, item: TokenStream2) -> syn::Result<TokenStream2> {
+    let Args {
+        year,
+        day,
+        title,
+        tags,
+    } = parse_args(args)?;
+    let mut item: ItemImpl = syn::parse2(item)?;
+
+    if item.trait_.is_none() {
+        return Err(syn::Error::new_spanned(
+            &item.self_ty,
+            "expected `impl Solution for ...`",
+        ));
+    }
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &item.generics,
+            "a registered solution can't be generic",
+        ));
+    }
+    let Type::Path(solution) = &*item.self_ty else {
+        return Err(syn::Error::new_spanned(
+            &item.self_ty,
+            "expected the name of a unit struct",
+        ));
+    };
+    let solution = solution.clone();
+
+    for existing in &item.items {
+        if let ImplItem::Fn(f) = existing {
+            if ["year", "day", "title", "tags"]
+                .iter()
+                .any(|name| f.sig.ident == name)
+            {
+                return Err(syn::Error::new_spanned(
+                    &f.sig.ident,
+                    "this is set by #[aoc::solution]",
+                ));
+            }
+        }
+    }
+
+    let methods: ItemImpl = syn::parse_quote! {
+        impl Methods {
+            fn year(&self) -> ::aoc::Year {
+                ::aoc::Year(#year)
+            }
+
+            fn day(&self) -> ::aoc::Day {
+                ::aoc::Day(#day)
+            }
+
+            fn title(&self) -> &'static str {
+                #title
+            }
+        }
+    };
+    item.items.splice(0..0, methods.items);
+
+    if tags.iter().any(|part| !part.is_empty()) {
+        let [part1, part2] = tags;
+        item.items.insert(
+            3,
+            syn::parse_quote! {
+                fn tags(&self, part: ::aoc::Part) -> &'static [::aoc::Tag] {
+                    match part {
+                        ::aoc::Part::One => &[#(#part1),*],
+                        ::aoc::Part::Two => &[#(#part2),*],
+                    }
+                }
+            },
+        );
+    }
+
+    Ok(quote! {
+        #item
+
+        ::aoc::inventory::submit! {
+            ::aoc::Registered(&#solution)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(args: TokenStream2, item: TokenStream2) -> String {
+        match expand(args, item) {
+            Ok(tokens) => panic!("Expanded to {tokens}"),
+            Err(error) => error.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_expand() {
+        let tokens = expand(
+            quote!(year = 2024, day = 6, title = "Guard Gallivant"),
+            quote!(
+                impl Solution for GuardGallivant {
+                    type Parsed<'a> = &'a str;
+                }
+            ),
+        )
+        .unwrap()
+        .to_string();
+
+        assert!(tokens.contains("fn day (& self) -> :: aoc :: Day { :: aoc :: Day (6) }"));
+        assert!(tokens.contains("\"Guard Gallivant\""));
+        assert!(tokens.contains("type Parsed < 'a > = & 'a str ;"));
+        assert!(tokens.contains(":: aoc :: Registered (& GuardGallivant)"));
+        assert!(!tokens.contains("fn tags"));
+
+        let tokens = expand(
+            quote!(
+                year = 2015,
+                day = 19,
+                title = "Medicine",
+                tags = [slow],
+                part2_tags = [broken]
+            ),
+            quote!(impl Solution for MedicineForRudolph {}),
+        )
+        .unwrap()
+        .to_string();
+
+        assert!(tokens.contains(":: aoc :: Part :: One => & [:: aoc :: Tag :: Slow]"));
+        assert!(tokens.contains(
+            ":: aoc :: Part :: Two => & [:: aoc :: Tag :: Slow , :: aoc :: Tag :: Broken]"
+        ));
+    }
+
+    #[test]
+    fn test_errors() {
+        let item = quote!(impl Solution for Foo {});
+        assert_eq!(
+            error(quote!(year = 2024, day = 26, title = "Foo"), item.clone()),
+            "the last day is 25"
+        );
+        assert_eq!(
+            error(quote!(year = 2024, title = "Foo"), item.clone()),
+            "missing `day`"
+        );
+        assert_eq!(
+            error(quote!(year = 2024, day = 1, name = "Foo"), item),
+            "expected `year`, `day`, `title` or tags"
+        );
+        assert_eq!(
+            error(
+                quote!(year = 2024, day = 1, title = "Foo", tags = [fast]),
+                quote!(impl Solution for Foo {})
+            ),
+            "expected `slow`, `broken` or `experimental`"
+        );
+        assert_eq!(
+            error(
+                quote!(year = 2024, day = 1, title = "Foo"),
+                quote!(impl Solution for Foo {
+                    fn day(&self) -> Day {
+                        Day(2)
+                    }
+                })
+            ),
+            "this is set by #[aoc::solution]"
+        );
+    }
+}